@@ -0,0 +1,246 @@
+use super::{HttpError, HttpStatus};
+use tracing::{trace, warn};
+
+const MAX_EXTENSION_LENGTH: usize = 1024;
+const MAX_TRAILERS_LENGTH: usize = 8192;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Size,
+    SizeWhitespace,
+    Extension,
+    SizeLf,
+    Data(usize),
+    DataCr,
+    DataLf,
+    TrailerStart,
+    Trailer,
+    TrailerLf,
+    EndLf,
+    Done,
+}
+
+#[derive(Debug)]
+pub struct ChunkedDecoder {
+    state: State,
+    size: usize,
+    digits: usize,
+    extension_length: usize,
+    trailers_length: usize,
+}
+
+impl Default for ChunkedDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChunkedDecoder {
+    pub fn new() -> Self {
+        Self {
+            state: State::Size,
+            size: 0,
+            digits: 0,
+            extension_length: 0,
+            trailers_length: 0,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.state == State::Done
+    }
+
+    pub fn decode(&mut self, input: &[u8], output: &mut Vec<u8>) -> Result<usize, HttpError> {
+        let mut position: usize = 0;
+
+        while position < input.len() && self.state != State::Done {
+            if let State::Data(remaining) = self.state {
+                let available: usize = remaining.min(input.len() - position);
+                output.extend_from_slice(&input[position..position + available]);
+                position += available;
+
+                self.state = match remaining - available {
+                    0 => State::DataCr,
+                    left => State::Data(left),
+                };
+
+                continue;
+            }
+
+            self.state = self.next_state(input[position])?;
+            position += 1;
+        }
+
+        Ok(position)
+    }
+
+    fn next_state(&mut self, byte: u8) -> Result<State, HttpError> {
+        match (self.state, byte) {
+            (State::Size, b'\r') if self.digits > 0 => Ok(State::SizeLf),
+            (State::Size, b';') if self.digits > 0 => Ok(State::Extension),
+            (State::Size, b' ' | b'\t') if self.digits > 0 => Ok(State::SizeWhitespace),
+            (State::Size, _) => {
+                self.push_size_digit(byte)?;
+                Ok(State::Size)
+            }
+            (State::SizeWhitespace, b' ' | b'\t') => Ok(State::SizeWhitespace),
+            (State::SizeWhitespace, b';') => Ok(State::Extension),
+            (State::SizeWhitespace, b'\r') => Ok(State::SizeLf),
+            (State::Extension, b'\r') => Ok(State::SizeLf),
+            (State::Extension, _) => {
+                self.extension_length += 1;
+
+                if self.extension_length > MAX_EXTENSION_LENGTH {
+                    warn!("Chunk extension exceeds {MAX_EXTENSION_LENGTH} bytes");
+                    return Err(HttpError::new(HttpStatus::BadRequest, "Chunk extension too long"));
+                }
+
+                Ok(State::Extension)
+            }
+            (State::SizeLf, b'\n') => {
+                let size: usize = self.size;
+                trace!("Decoding chunk of {size} bytes");
+
+                self.size = 0;
+                self.digits = 0;
+                self.extension_length = 0;
+
+                Ok(if size == 0 {
+                    State::TrailerStart
+                } else {
+                    State::Data(size)
+                })
+            }
+            (State::DataCr, b'\r') => Ok(State::DataLf),
+            (State::DataLf, b'\n') => Ok(State::Size),
+            (State::TrailerStart, b'\r') => Ok(State::EndLf),
+            (State::TrailerStart | State::Trailer, _) => {
+                self.trailers_length += 1;
+
+                if self.trailers_length > MAX_TRAILERS_LENGTH {
+                    warn!("Chunked trailers exceed {MAX_TRAILERS_LENGTH} bytes");
+                    return Err(HttpError::new(HttpStatus::BadRequest, "Chunked trailers too long"));
+                }
+
+                Ok(if byte == b'\r' {
+                    State::TrailerLf
+                } else {
+                    State::Trailer
+                })
+            }
+            (State::TrailerLf, b'\n') => Ok(State::TrailerStart),
+            (State::EndLf, b'\n') => {
+                trace!("Chunked body fully decoded");
+                Ok(State::Done)
+            }
+            (state, _) => {
+                warn!("Unexpected byte 0x{byte:02x} in chunked body while in {state:?}");
+                Err(HttpError::new(HttpStatus::BadRequest, "Malformed chunked encoding"))
+            }
+        }
+    }
+
+    fn push_size_digit(&mut self, byte: u8) -> Result<(), HttpError> {
+        let digit: usize = char::from(byte).to_digit(16).ok_or_else(|| {
+            warn!("Invalid chunk size digit: 0x{byte:02x}");
+            HttpError::new(HttpStatus::BadRequest, "Invalid chunk size")
+        })? as usize;
+
+        self.size = self
+            .size
+            .checked_mul(16)
+            .and_then(|size: usize| size.checked_add(digit))
+            .ok_or_else(|| {
+                warn!("Chunk size overflow");
+                HttpError::new(HttpStatus::BadRequest, "Chunk size too large")
+            })?;
+
+        self.digits += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_all(input: &[u8]) -> Result<(Vec<u8>, usize, bool), HttpError> {
+        let mut decoder: ChunkedDecoder = ChunkedDecoder::new();
+        let mut output: Vec<u8> = Vec::new();
+        let consumed: usize = decoder.decode(input, &mut output)?;
+        Ok((output, consumed, decoder.is_done()))
+    }
+
+    #[test]
+    fn test_decode_simple_body() {
+        let input: &[u8] = b"5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n";
+        let (output, consumed, done) = decode_all(input).unwrap();
+
+        assert!(done);
+        assert_eq!(consumed, input.len());
+        assert_eq!(output, b"hello world");
+    }
+
+    #[test]
+    fn test_decode_stops_at_end_of_body() {
+        let input: &[u8] = b"3\r\nabc\r\n0\r\n\r\nGET / HTTP/1.1\r\n\r\n";
+        let (output, consumed, done) = decode_all(input).unwrap();
+
+        assert!(done);
+        assert_eq!(output, b"abc");
+        assert_eq!(&input[consumed..], b"GET / HTTP/1.1\r\n\r\n");
+    }
+
+    #[test]
+    fn test_decode_extensions_and_trailers() {
+        let input: &[u8] = b"A;name=value ; other\r\n0123456789\r\n0 ;last\r\nExpires: never\r\nX-Trailer: 1\r\n\r\n";
+        let (output, consumed, done) = decode_all(input).unwrap();
+
+        assert!(done);
+        assert_eq!(consumed, input.len());
+        assert_eq!(output, b"0123456789");
+    }
+
+    #[test]
+    fn test_decode_across_multiple_reads() {
+        let input: &[u8] = b"4\r\nWiki\r\n5\r\npedia\r\n0\r\n\r\n";
+        let mut decoder: ChunkedDecoder = ChunkedDecoder::new();
+        let mut output: Vec<u8> = Vec::new();
+
+        for byte in input.chunks(1) {
+            assert!(!decoder.is_done());
+            assert_eq!(decoder.decode(byte, &mut output).unwrap(), 1);
+        }
+
+        assert!(decoder.is_done());
+        assert_eq!(output, b"Wikipedia");
+    }
+
+    #[test]
+    fn test_decode_incomplete_body() {
+        let (output, _, done) = decode_all(b"5\r\nhel").unwrap();
+
+        assert!(!done);
+        assert_eq!(output, b"hel");
+    }
+
+    #[test]
+    fn test_invalid_chunk_size() {
+        for input in [&b"z\r\n"[..], b"\r\n", b";ext\r\n", b"-1\r\n", b"5 x\r\nhello\r\n"] {
+            let result: Result<(Vec<u8>, usize, bool), HttpError> = decode_all(input);
+            assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest);
+        }
+    }
+
+    #[test]
+    fn test_chunk_size_overflow() {
+        let result: Result<(Vec<u8>, usize, bool), HttpError> = decode_all(b"fffffffffffffffffffff\r\n");
+        assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest);
+    }
+
+    #[test]
+    fn test_missing_crlf_after_data() {
+        let result: Result<(Vec<u8>, usize, bool), HttpError> = decode_all(b"3\r\nabcd\r\n0\r\n\r\n");
+        assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest);
+    }
+}
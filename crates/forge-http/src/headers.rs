@@ -0,0 +1,466 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::str::Utf8Error;
+
+use super::{HttpError, HttpStatus};
+use tracing::warn;
+
+type HeaderEntry<'a> = (HeaderName<'a>, HeaderValue<'a>);
+
+const MAX_STANDARD_HEADER_LENGTH: usize = 30;
+
+fn standard_header(name: &str) -> Option<(&'static str, &'static str)> {
+    let mut buffer: [u8; MAX_STANDARD_HEADER_LENGTH] = [0; MAX_STANDARD_HEADER_LENGTH];
+    let lowercase: &mut [u8] = buffer.get_mut(..name.len())?;
+    lowercase.copy_from_slice(name.as_bytes());
+    lowercase.make_ascii_lowercase();
+
+    Some(match str::from_utf8(lowercase).ok()? {
+        "accept" => ("accept", "Accept"),
+        "accept-charset" => ("accept-charset", "Accept-Charset"),
+        "accept-encoding" => ("accept-encoding", "Accept-Encoding"),
+        "accept-language" => ("accept-language", "Accept-Language"),
+        "accept-ranges" => ("accept-ranges", "Accept-Ranges"),
+        "access-control-allow-headers" => ("access-control-allow-headers", "Access-Control-Allow-Headers"),
+        "access-control-allow-methods" => ("access-control-allow-methods", "Access-Control-Allow-Methods"),
+        "access-control-allow-origin" => ("access-control-allow-origin", "Access-Control-Allow-Origin"),
+        "access-control-request-headers" => ("access-control-request-headers", "Access-Control-Request-Headers"),
+        "access-control-request-method" => ("access-control-request-method", "Access-Control-Request-Method"),
+        "age" => ("age", "Age"),
+        "allow" => ("allow", "Allow"),
+        "authorization" => ("authorization", "Authorization"),
+        "cache-control" => ("cache-control", "Cache-Control"),
+        "connection" => ("connection", "Connection"),
+        "content-disposition" => ("content-disposition", "Content-Disposition"),
+        "content-encoding" => ("content-encoding", "Content-Encoding"),
+        "content-language" => ("content-language", "Content-Language"),
+        "content-length" => ("content-length", "Content-Length"),
+        "content-location" => ("content-location", "Content-Location"),
+        "content-range" => ("content-range", "Content-Range"),
+        "content-type" => ("content-type", "Content-Type"),
+        "cookie" => ("cookie", "Cookie"),
+        "date" => ("date", "Date"),
+        "etag" => ("etag", "ETag"),
+        "expect" => ("expect", "Expect"),
+        "expires" => ("expires", "Expires"),
+        "forwarded" => ("forwarded", "Forwarded"),
+        "host" => ("host", "Host"),
+        "if-match" => ("if-match", "If-Match"),
+        "if-modified-since" => ("if-modified-since", "If-Modified-Since"),
+        "if-none-match" => ("if-none-match", "If-None-Match"),
+        "if-range" => ("if-range", "If-Range"),
+        "if-unmodified-since" => ("if-unmodified-since", "If-Unmodified-Since"),
+        "keep-alive" => ("keep-alive", "Keep-Alive"),
+        "last-modified" => ("last-modified", "Last-Modified"),
+        "location" => ("location", "Location"),
+        "origin" => ("origin", "Origin"),
+        "pragma" => ("pragma", "Pragma"),
+        "range" => ("range", "Range"),
+        "referer" => ("referer", "Referer"),
+        "retry-after" => ("retry-after", "Retry-After"),
+        "sec-websocket-accept" => ("sec-websocket-accept", "Sec-WebSocket-Accept"),
+        "sec-websocket-extensions" => ("sec-websocket-extensions", "Sec-WebSocket-Extensions"),
+        "sec-websocket-key" => ("sec-websocket-key", "Sec-WebSocket-Key"),
+        "sec-websocket-protocol" => ("sec-websocket-protocol", "Sec-WebSocket-Protocol"),
+        "sec-websocket-version" => ("sec-websocket-version", "Sec-WebSocket-Version"),
+        "server" => ("server", "Server"),
+        "set-cookie" => ("set-cookie", "Set-Cookie"),
+        "te" => ("te", "TE"),
+        "trailer" => ("trailer", "Trailer"),
+        "transfer-encoding" => ("transfer-encoding", "Transfer-Encoding"),
+        "upgrade" => ("upgrade", "Upgrade"),
+        "user-agent" => ("user-agent", "User-Agent"),
+        "vary" => ("vary", "Vary"),
+        "www-authenticate" => ("www-authenticate", "WWW-Authenticate"),
+        _ => return None,
+    })
+}
+
+pub(crate) fn is_token_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte)
+}
+
+pub(crate) fn is_token(value: &str) -> bool {
+    !value.is_empty() && value.bytes().all(is_token_char)
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct HeaderName<'a> {
+    name: Cow<'a, str>,
+    canonical: Option<&'static str>,
+}
+
+impl<'a> HeaderName<'a> {
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        let name: Cow<str> = name.into();
+
+        if let Some((standard, canonical)) = standard_header(&name) {
+            return Self {
+                name: Cow::Borrowed(standard),
+                canonical: Some(canonical),
+            };
+        }
+
+        let name: Cow<str> = match name.bytes().any(|byte: u8| byte.is_ascii_uppercase()) {
+            true => Cow::Owned(name.to_ascii_lowercase()),
+            false => name,
+        };
+
+        Self { name, canonical: None }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+
+    pub fn canonical(&self) -> &str {
+        self.canonical.unwrap_or(&self.name)
+    }
+
+    pub fn into_owned(self) -> HeaderName<'static> {
+        HeaderName {
+            name: Cow::Owned(self.name.into_owned()),
+            canonical: self.canonical,
+        }
+    }
+}
+
+impl fmt::Debug for HeaderName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.name)
+    }
+}
+
+impl fmt::Display for HeaderName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<'a> From<&'a str> for HeaderName<'a> {
+    fn from(name: &'a str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for HeaderName<'_> {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for HeaderName<'a> {
+    fn from(name: Cow<'a, str>) -> Self {
+        Self::new(name)
+    }
+}
+
+impl PartialEq<str> for HeaderName<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.name.eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialEq<&str> for HeaderName<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.name.eq_ignore_ascii_case(other)
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct HeaderMap<'a> {
+    entries: Vec<HeaderEntry<'a>>,
+}
+
+impl<'a> HeaderMap<'a> {
+    pub fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&HeaderValue<'a>> {
+        self.entries
+            .iter()
+            .find(|(key, _): &&HeaderEntry| *key == name)
+            .map(|(_, value): &HeaderEntry| value)
+    }
+
+    pub fn get_all<'b>(&'b self, name: &'b str) -> impl Iterator<Item = &'b HeaderValue<'a>> {
+        self.entries
+            .iter()
+            .filter(move |(key, _): &&HeaderEntry| *key == name)
+            .map(|(_, value): &HeaderEntry| value)
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    pub fn append<K, V>(&mut self, name: K, value: V)
+    where
+        K: Into<HeaderName<'a>>,
+        V: Into<HeaderValue<'a>>,
+    {
+        self.entries.push((name.into(), value.into()));
+    }
+
+    pub fn insert<K, V>(&mut self, name: K, value: V) -> Option<HeaderValue<'a>>
+    where
+        K: Into<HeaderName<'a>>,
+        V: Into<HeaderValue<'a>>,
+    {
+        let name: HeaderName = name.into();
+        let previous: Option<HeaderValue> = self.remove(name.as_str());
+        self.entries.push((name, value.into()));
+        previous
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<HeaderValue<'a>> {
+        let position: usize = self.entries.iter().position(|(key, _): &HeaderEntry| *key == name)?;
+        let (_, removed): HeaderEntry = self.entries.remove(position);
+        self.entries.retain(|(key, _): &HeaderEntry| *key != name);
+        Some(removed)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&HeaderName<'a>, &HeaderValue<'a>)> {
+        self.entries.iter().map(|(key, value): &HeaderEntry| (key, value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn into_owned(self) -> HeaderMap<'static> {
+        HeaderMap {
+            entries: self
+                .entries
+                .into_iter()
+                .map(|(key, value): HeaderEntry| (key.into_owned(), value.into_owned()))
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Debug for HeaderMap<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K, V> FromIterator<(K, V)> for HeaderMap<'a>
+where
+    K: Into<HeaderName<'a>>,
+    V: Into<HeaderValue<'a>>,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut headers: HeaderMap = HeaderMap::new();
+        headers.extend(iter);
+        headers
+    }
+}
+
+impl<'a, K, V> Extend<(K, V)> for HeaderMap<'a>
+where
+    K: Into<HeaderName<'a>>,
+    V: Into<HeaderValue<'a>>,
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.append(key, value);
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct HeaderValue<'a>(Cow<'a, [u8]>);
+
+impl<'a> HeaderValue<'a> {
+    pub fn from_bytes<T>(bytes: T) -> Self
+    where
+        T: Into<Cow<'a, [u8]>>,
+    {
+        Self(bytes.into())
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn to_str(&self) -> Result<&str, HttpError> {
+        str::from_utf8(&self.0).map_err(|e: Utf8Error| {
+            warn!("Header value is not valid UTF-8: {e}");
+            HttpError::new(HttpStatus::BadRequest, format!("Header value is not valid UTF-8: {e}"))
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn into_owned(self) -> HeaderValue<'static> {
+        HeaderValue(Cow::Owned(self.0.into_owned()))
+    }
+}
+
+impl fmt::Debug for HeaderValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", String::from_utf8_lossy(&self.0))
+    }
+}
+
+impl fmt::Display for HeaderValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(&self.0))
+    }
+}
+
+impl<'a> From<&'a [u8]> for HeaderValue<'a> {
+    fn from(value: &'a [u8]) -> Self {
+        Self(Cow::Borrowed(value))
+    }
+}
+
+impl<'a> From<&'a str> for HeaderValue<'a> {
+    fn from(value: &'a str) -> Self {
+        Self(Cow::Borrowed(value.as_bytes()))
+    }
+}
+
+impl From<String> for HeaderValue<'_> {
+    fn from(value: String) -> Self {
+        Self(Cow::Owned(value.into_bytes()))
+    }
+}
+
+impl From<Vec<u8>> for HeaderValue<'_> {
+    fn from(value: Vec<u8>) -> Self {
+        Self(Cow::Owned(value))
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for HeaderValue<'a> {
+    fn from(value: Cow<'a, str>) -> Self {
+        match value {
+            Cow::Borrowed(value) => Self::from(value),
+            Cow::Owned(value) => Self::from(value),
+        }
+    }
+}
+
+impl PartialEq<str> for HeaderValue<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl PartialEq<&str> for HeaderValue<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_name_standard_names_are_static() {
+        let name: HeaderName = HeaderName::from(String::from("Content-Type"));
+
+        assert_eq!(name.as_str(), "content-type");
+        assert!(matches!(name.name, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_header_name_canonical_casing() {
+        assert_eq!(HeaderName::from("content-type").canonical(), "Content-Type");
+        assert_eq!(HeaderName::from("ETAG").canonical(), "ETag");
+        assert_eq!(HeaderName::from("sec-websocket-accept").canonical(), "Sec-WebSocket-Accept");
+        assert_eq!(HeaderName::from("X-Request-Id").canonical(), "x-request-id");
+        assert_eq!(
+            HeaderName::from("access-control-request-headers-extended").canonical(),
+            "access-control-request-headers-extended"
+        );
+    }
+
+    #[test]
+    fn test_header_name_custom_names_are_lowercased() {
+        let borrowed: HeaderName = HeaderName::from("x-request-id");
+        assert!(matches!(borrowed.name, Cow::Borrowed("x-request-id")));
+
+        let owned: HeaderName = HeaderName::from("X-Request-Id");
+        assert_eq!(owned.as_str(), "x-request-id");
+        assert!(matches!(owned.name, Cow::Owned(_)));
+    }
+
+    #[test]
+    fn test_header_map_case_insensitive_lookup() {
+        let mut headers: HeaderMap = HeaderMap::new();
+        headers.append("Content-Type", "application/json");
+
+        assert_eq!(headers.get("content-type").unwrap(), "application/json");
+        assert_eq!(headers.get("CONTENT-TYPE").unwrap(), "application/json");
+        assert!(headers.contains_key("Content-type"));
+        assert!(!headers.contains_key("content-length"));
+    }
+
+    #[test]
+    fn test_header_map_repeated_headers_keep_order() {
+        let headers: HeaderMap = [
+            ("Accept", "text/html"),
+            ("Cookie", "a=1"),
+            ("accept", "application/json"),
+            ("Cookie", "b=2"),
+        ]
+        .into_iter()
+        .collect();
+
+        let accept: Vec<&HeaderValue> = headers.get_all("accept").collect();
+        assert_eq!(accept, vec!["text/html", "application/json"]);
+        assert_eq!(headers.get("cookie").unwrap(), "a=1");
+
+        let names: Vec<&str> = headers.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["accept", "cookie", "accept", "cookie"]);
+    }
+
+    #[test]
+    fn test_header_map_insert_replaces_all_values() {
+        let mut headers: HeaderMap = HeaderMap::new();
+        headers.append("Vary", "Accept");
+        headers.append("Host", "localhost");
+        headers.append("Vary", "Origin");
+
+        let previous: Option<HeaderValue> = headers.insert("vary", "*");
+
+        assert_eq!(previous.unwrap(), "Accept");
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers.get_all("vary").collect::<Vec<&HeaderValue>>(), vec!["*"]);
+    }
+
+    #[test]
+    fn test_header_map_remove() {
+        let mut headers: HeaderMap = HeaderMap::new();
+        headers.append("Forwarded", "for=1.1.1.1");
+        headers.append("Forwarded", "for=2.2.2.2");
+
+        assert_eq!(headers.remove("forwarded").unwrap(), "for=1.1.1.1");
+        assert!(headers.is_empty());
+        assert!(headers.remove("forwarded").is_none());
+    }
+}
@@ -1,11 +1,30 @@
+pub mod chunked;
 pub mod error;
+pub mod headers;
+pub mod limits;
 pub mod method;
+pub mod query;
 pub mod request;
 pub mod response;
 pub mod status;
+pub mod typed_headers;
+pub mod upgrade;
+pub mod uri;
+pub mod validation;
+pub mod version;
+pub mod websocket;
 
+pub use chunked::ChunkedDecoder;
 pub use error::HttpError;
-pub use method::HttpMethod;
-pub use request::{Headers, Params, Request};
+pub use headers::{HeaderMap, HeaderName, HeaderValue};
+pub use limits::Limits;
+pub use method::{ExtensionMethod, HttpMethod};
+pub use query::Query;
+pub use request::{Params, Request};
 pub use response::{IntoResponse, Response};
 pub use status::HttpStatus;
+pub use typed_headers::TypedHeader;
+pub use upgrade::{OnUpgrade, Upgraded};
+pub use validation::ParseOptions;
+pub use version::HttpVersion;
+pub use websocket::{CloseFrame, Message, Role, WebSocket, WebSocketConfig, WebSocketError, WebSocketUpgrade};
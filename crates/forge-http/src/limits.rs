@@ -0,0 +1,218 @@
+use super::{HeaderName, HeaderValue, HttpError, HttpStatus, Request};
+use tracing::warn;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Limits {
+    pub max_uri_length: usize,
+    pub max_head_size: usize,
+    pub max_headers: usize,
+    pub max_header_size: usize,
+    pub max_body_size: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_uri_length: 8 * 1024,
+            max_head_size: 16 * 1024,
+            max_headers: 100,
+            max_header_size: 8 * 1024,
+            max_body_size: 2 * 1024 * 1024,
+        }
+    }
+}
+
+impl Limits {
+    pub fn check_uri_length(&self, length: usize) -> Result<(), HttpError> {
+        if length > self.max_uri_length {
+            warn!("Request target of {length} bytes exceeds limit of {}", self.max_uri_length);
+            return Err(HttpError::new(HttpStatus::UriTooLong, "Request target is too long"));
+        }
+
+        Ok(())
+    }
+
+    pub fn check_head_size(&self, length: usize) -> Result<(), HttpError> {
+        if length > self.max_head_size {
+            warn!("Request head of {length} bytes exceeds limit of {}", self.max_head_size);
+            return Err(HttpError::new(
+                HttpStatus::RequestHeaderFieldsTooLarge,
+                "Request head is too large",
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn check_header_count(&self, count: usize) -> Result<(), HttpError> {
+        if count > self.max_headers {
+            warn!("Request has {count} headers, limit is {}", self.max_headers);
+            return Err(HttpError::new(
+                HttpStatus::RequestHeaderFieldsTooLarge,
+                "Request has too many headers",
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn check_header_size(&self, length: usize) -> Result<(), HttpError> {
+        if length > self.max_header_size {
+            warn!("Header field of {length} bytes exceeds limit of {}", self.max_header_size);
+            return Err(HttpError::new(
+                HttpStatus::RequestHeaderFieldsTooLarge,
+                "Request header field is too large",
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn check_header_field(&self, name: &str, value: &[u8]) -> Result<(), HttpError> {
+        self.check_header_size(name.len() + value.len())
+    }
+
+    pub fn check_body_size(&self, length: usize) -> Result<(), HttpError> {
+        if length > self.max_body_size {
+            warn!("Request body of {length} bytes exceeds limit of {}", self.max_body_size);
+            return Err(HttpError::new(HttpStatus::PayloadTooLarge, "Request body is too large"));
+        }
+
+        Ok(())
+    }
+
+    pub fn within(&self, global: &Limits) -> Limits {
+        Limits {
+            max_uri_length: self.max_uri_length.min(global.max_uri_length),
+            max_head_size: self.max_head_size.min(global.max_head_size),
+            max_headers: self.max_headers.min(global.max_headers),
+            max_header_size: self.max_header_size.min(global.max_header_size),
+            max_body_size: self.max_body_size,
+        }
+    }
+
+    pub fn check_request(&self, request: &Request, head_length: usize) -> Result<(), HttpError> {
+        self.check_head_size(head_length)?;
+        self.check_uri_length(request.target.len())?;
+        self.check_header_count(request.headers.len())?;
+
+        request
+            .headers
+            .iter()
+            .try_for_each(|(name, value): (&HeaderName, &HeaderValue)| {
+                self.check_header_field(name.as_str(), value.as_bytes())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseOptions;
+
+    fn limits() -> Limits {
+        Limits {
+            max_uri_length: 16,
+            max_head_size: 128,
+            max_headers: 2,
+            max_header_size: 32,
+            max_body_size: 8,
+        }
+    }
+
+    #[test]
+    fn test_limit_statuses() {
+        let limits: Limits = limits();
+
+        assert!(limits.check_uri_length(16).is_ok());
+        assert_eq!(limits.check_uri_length(17).unwrap_err().status, HttpStatus::UriTooLong);
+        assert_eq!(
+            limits.check_head_size(129).unwrap_err().status,
+            HttpStatus::RequestHeaderFieldsTooLarge
+        );
+        assert_eq!(
+            limits.check_header_count(3).unwrap_err().status,
+            HttpStatus::RequestHeaderFieldsTooLarge
+        );
+        assert_eq!(
+            limits.check_header_size(33).unwrap_err().status,
+            HttpStatus::RequestHeaderFieldsTooLarge
+        );
+        assert!(limits.check_body_size(8).is_ok());
+        assert_eq!(limits.check_body_size(9).unwrap_err().status, HttpStatus::PayloadTooLarge);
+    }
+
+    #[test]
+    fn test_check_request_against_stricter_limits() {
+        let raw: &[u8] = b"GET /a/very/long/path/here HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        let (request, head_length): (Request, usize) = Request::parse(raw).unwrap();
+
+        assert!(Limits::default().check_request(&request, head_length).is_ok());
+        assert_eq!(
+            limits().check_request(&request, head_length).unwrap_err().status,
+            HttpStatus::UriTooLong
+        );
+    }
+
+    #[test]
+    fn test_route_limits_within_global() {
+        let route: Limits = Limits {
+            max_uri_length: 64 * 1024,
+            max_header_size: 4,
+            max_body_size: 64 * 1024 * 1024,
+            ..Limits::default()
+        };
+        let limits: Limits = route.within(&limits());
+
+        assert_eq!(limits.max_uri_length, 16);
+        assert_eq!(limits.max_head_size, 128);
+        assert_eq!(limits.max_headers, 2);
+        assert_eq!(limits.max_header_size, 4);
+        assert_eq!(limits.max_body_size, 64 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_header_size_matches_parser() {
+        let raw: &[u8] = b"GET / HTTP/1.1\r\nHost:    0123456789abcdef0123456789a   \r\n\r\n";
+        let limits: Limits = Limits {
+            max_header_size: 31,
+            ..limits()
+        };
+
+        let (request, head_length): (Request, usize) =
+            Request::parse_with(raw, &ParseOptions::lenient(), &limits).unwrap();
+        assert!(limits.check_request(&request, head_length).is_ok());
+
+        let stricter: Limits = Limits {
+            max_header_size: 30,
+            ..limits
+        };
+        assert!(Request::parse_with(raw, &ParseOptions::lenient(), &stricter).is_err());
+        assert!(stricter.check_request(&request, head_length).is_err());
+    }
+
+    #[test]
+    fn test_parse_enforces_limits() {
+        let parse = |raw: &'static [u8]| {
+            Request::parse_with(raw, &ParseOptions::strict(), &limits())
+                .map(|_| ())
+                .unwrap_err()
+                .status
+        };
+
+        assert_eq!(
+            parse(b"GET /0123456789abcdef HTTP/1.1\r\nHost: a\r\n\r\n"),
+            HttpStatus::UriTooLong
+        );
+        assert_eq!(
+            parse(b"GET / HTTP/1.1\r\nHost: a\r\nA: 1\r\nB: 2\r\n\r\n"),
+            HttpStatus::RequestHeaderFieldsTooLarge
+        );
+        assert_eq!(
+            parse(b"GET / HTTP/1.1\r\nHost: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\r\n\r\n"),
+            HttpStatus::RequestHeaderFieldsTooLarge
+        );
+        assert_eq!(parse(&[b'a'; 256]), HttpStatus::RequestHeaderFieldsTooLarge);
+        assert_eq!(parse(b"GET / HTTP/1.1\r\nHost: a\r\n"), HttpStatus::BadRequest);
+    }
+}
@@ -1,10 +1,12 @@
 use core::fmt;
+use std::borrow::Cow;
 use std::str;
 
 use super::HttpError;
 use super::HttpStatus;
+use super::headers::is_token;
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum HttpMethod {
     GET,
     POST,
@@ -14,11 +16,46 @@ pub enum HttpMethod {
     HEAD,
     OPTIONS,
     TRACE,
+    CONNECT,
+    PROPFIND,
+    PROPPATCH,
+    MKCOL,
+    COPY,
+    MOVE,
+    LOCK,
+    UNLOCK,
+    REPORT,
+    Extension(ExtensionMethod),
 }
 
-impl fmt::Display for HttpMethod {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let msg: &'static str = match self {
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct ExtensionMethod(Cow<'static, str>);
+
+impl ExtensionMethod {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl HttpMethod {
+    fn extension<T>(name: T) -> Result<Self, HttpError>
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let name: Cow<'static, str> = name.into();
+
+        match Self::from_str_known(&name) {
+            Some(method) => Ok(method),
+            None if is_token(&name) => Ok(HttpMethod::Extension(ExtensionMethod(name))),
+            None => Err(HttpError::new(
+                HttpStatus::BadRequest,
+                format!("Invalid HTTP method token: \"{name}\""),
+            )),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
             HttpMethod::GET => "GET",
             HttpMethod::POST => "POST",
             HttpMethod::PUT => "PUT",
@@ -27,9 +64,46 @@ impl fmt::Display for HttpMethod {
             HttpMethod::HEAD => "HEAD",
             HttpMethod::OPTIONS => "OPTIONS",
             HttpMethod::TRACE => "TRACE",
-        };
+            HttpMethod::CONNECT => "CONNECT",
+            HttpMethod::PROPFIND => "PROPFIND",
+            HttpMethod::PROPPATCH => "PROPPATCH",
+            HttpMethod::MKCOL => "MKCOL",
+            HttpMethod::COPY => "COPY",
+            HttpMethod::MOVE => "MOVE",
+            HttpMethod::LOCK => "LOCK",
+            HttpMethod::UNLOCK => "UNLOCK",
+            HttpMethod::REPORT => "REPORT",
+            HttpMethod::Extension(method) => method.as_str(),
+        }
+    }
+
+    fn from_str_known(s: &str) -> Option<Self> {
+        match s {
+            "GET" => Some(HttpMethod::GET),
+            "POST" => Some(HttpMethod::POST),
+            "PUT" => Some(HttpMethod::PUT),
+            "DELETE" => Some(HttpMethod::DELETE),
+            "PATCH" => Some(HttpMethod::PATCH),
+            "HEAD" => Some(HttpMethod::HEAD),
+            "OPTIONS" => Some(HttpMethod::OPTIONS),
+            "TRACE" => Some(HttpMethod::TRACE),
+            "CONNECT" => Some(HttpMethod::CONNECT),
+            "PROPFIND" => Some(HttpMethod::PROPFIND),
+            "PROPPATCH" => Some(HttpMethod::PROPPATCH),
+            "MKCOL" => Some(HttpMethod::MKCOL),
+            "COPY" => Some(HttpMethod::COPY),
+            "MOVE" => Some(HttpMethod::MOVE),
+            "LOCK" => Some(HttpMethod::LOCK),
+            "UNLOCK" => Some(HttpMethod::UNLOCK),
+            "REPORT" => Some(HttpMethod::REPORT),
+            _ => None,
+        }
+    }
+}
 
-        write!(f, "{msg}")
+impl fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
     }
 }
 
@@ -37,19 +111,65 @@ impl str::FromStr for HttpMethod {
     type Err = HttpError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "GET" => Ok(HttpMethod::GET),
-            "POST" => Ok(HttpMethod::POST),
-            "PUT" => Ok(HttpMethod::PUT),
-            "DELETE" => Ok(HttpMethod::DELETE),
-            "PATCH" => Ok(HttpMethod::PATCH),
-            "HEAD" => Ok(HttpMethod::HEAD),
-            "OPTIONS" => Ok(HttpMethod::OPTIONS),
-            "TRACE" => Ok(HttpMethod::TRACE),
-            _ => Err(HttpError::new(
-                HttpStatus::BadRequest,
-                format!("Unknown or unsupported HTTP method: \"{s}\""),
-            )),
+        match Self::from_str_known(s) {
+            Some(method) => Ok(method),
+            None => Self::extension(s.to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_standard_and_webdav_methods() {
+        for name in [
+            "GET",
+            "CONNECT",
+            "PROPFIND",
+            "PROPPATCH",
+            "MKCOL",
+            "COPY",
+            "MOVE",
+            "LOCK",
+            "UNLOCK",
+            "REPORT",
+        ] {
+            let method: HttpMethod = HttpMethod::from_str(name).unwrap();
+
+            assert!(!matches!(method, HttpMethod::Extension(_)), "{name}");
+            assert_eq!(method.to_string(), name);
+        }
+    }
+
+    #[test]
+    fn test_parse_extension_method() {
+        let method: HttpMethod = HttpMethod::from_str("PURGE").unwrap();
+
+        assert_eq!(method, HttpMethod::Extension(ExtensionMethod(Cow::Borrowed("PURGE"))));
+        assert_eq!(method, HttpMethod::extension("PURGE").unwrap());
+        assert_eq!(method.as_str(), "PURGE");
+    }
+
+    #[test]
+    fn test_methods_are_case_sensitive() {
+        let method: HttpMethod = HttpMethod::from_str("get").unwrap();
+        assert_eq!(method, HttpMethod::Extension(ExtensionMethod(Cow::Borrowed("get"))));
+        assert_ne!(method, HttpMethod::GET);
+    }
+
+    #[test]
+    fn test_extension_resolves_known_methods() {
+        assert_eq!(HttpMethod::extension("MKCOL").unwrap(), HttpMethod::MKCOL);
+    }
+
+    #[test]
+    fn test_invalid_method_token() {
+        for name in ["", "GE T", "BAD(METHOD)", "ÜBER"] {
+            let error: HttpError = HttpMethod::from_str(name).unwrap_err();
+            assert_eq!(error.status, HttpStatus::BadRequest, "{name}");
         }
     }
 }
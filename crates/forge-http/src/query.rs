@@ -0,0 +1,143 @@
+use std::borrow::Cow;
+
+use super::uri::form_decode;
+use super::{HttpError, HttpStatus};
+use serde::Deserialize;
+use tracing::warn;
+
+const PAIRS_SEPARATOR: char = '&';
+const VALUE_SEPARATOR: char = '=';
+
+type QueryPair<'a> = (Cow<'a, str>, Cow<'a, str>);
+
+#[derive(Debug, Default)]
+pub struct Query<'a> {
+    raw: &'a str,
+    pairs: Vec<QueryPair<'a>>,
+}
+
+impl<'a> Query<'a> {
+    pub fn new(raw: &'a str) -> Result<Self, HttpError> {
+        let pairs: Vec<QueryPair> = raw
+            .split(PAIRS_SEPARATOR)
+            .filter(|pair: &&str| !pair.is_empty())
+            .map(|pair: &str| {
+                let (key, value): (&str, &str) = pair.split_once(VALUE_SEPARATOR).unwrap_or((pair, ""));
+                Ok((form_decode(key)?, form_decode(value)?))
+            })
+            .collect::<Result<Vec<QueryPair>, HttpError>>()?;
+
+        Ok(Self { raw, pairs })
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.raw
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(k, _): &&QueryPair| k == key)
+            .map(|(_, v): &QueryPair| v.as_ref())
+    }
+
+    pub fn get_all<'b>(&'b self, key: &'b str) -> impl Iterator<Item = &'b str> {
+        self.pairs
+            .iter()
+            .filter(move |(k, _): &&QueryPair| k == key)
+            .map(|(_, v): &QueryPair| v.as_ref())
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs.iter().map(|(k, v): &QueryPair| (k.as_ref(), v.as_ref()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    pub fn deserialize<T>(&self) -> Result<T, HttpError>
+    where
+        T: Deserialize<'a>,
+    {
+        serde_urlencoded::from_str(self.raw).map_err(|e: serde_urlencoded::de::Error| {
+            warn!("Failed to deserialize query string '{}': {e}", self.raw);
+            HttpError::new(HttpStatus::BadRequest, format!("Invalid query string: {e}"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Pagination {
+        page: u32,
+        limit: Option<u32>,
+        sort: String,
+    }
+
+    #[test]
+    fn test_parse_query_pairs() {
+        let query: Query = Query::new("id=1&name=john+doe&city=S%C3%A3o%20Paulo").unwrap();
+
+        assert_eq!(query.len(), 3);
+        assert_eq!(query.get("id"), Some("1"));
+        assert_eq!(query.get("name"), Some("john doe"));
+        assert_eq!(query.get("city"), Some("São Paulo"));
+        assert_eq!(query.get("missing"), None);
+    }
+
+    #[test]
+    fn test_query_multi_valued_pairs_keep_order() {
+        let query: Query = Query::new("tag=a&other=x&tag=b&tag=c").unwrap();
+
+        let tags: Vec<&str> = query.get_all("tag").collect();
+        assert_eq!(tags, vec!["a", "b", "c"]);
+        assert_eq!(query.get("tag"), Some("a"));
+    }
+
+    #[test]
+    fn test_query_keys_without_values_and_empty_pairs() {
+        let query: Query = Query::new("&flag&empty=&&x=1").unwrap();
+
+        assert_eq!(query.len(), 3);
+        assert!(query.contains_key("flag"));
+        assert_eq!(query.get("flag"), Some(""));
+        assert_eq!(query.get("empty"), Some(""));
+        assert_eq!(query.get("x"), Some("1"));
+    }
+
+    #[test]
+    fn test_query_invalid_encoding() {
+        let result: Result<Query, HttpError> = Query::new("name=%zz");
+        assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest);
+    }
+
+    #[test]
+    fn test_query_deserialize() {
+        let query: Query = Query::new("page=2&sort=name%20asc").unwrap();
+        let pagination: Pagination = query.deserialize().unwrap();
+
+        assert_eq!(pagination.page, 2);
+        assert_eq!(pagination.limit, None);
+        assert_eq!(pagination.sort, "name asc");
+    }
+
+    #[test]
+    fn test_query_deserialize_invalid_type() {
+        let query: Query = Query::new("page=two&sort=name").unwrap();
+        let result: Result<Pagination, HttpError> = query.deserialize();
+
+        assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest);
+    }
+}
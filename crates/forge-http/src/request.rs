@@ -1,78 +1,289 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
-use std::str::{FromStr, Lines, SplitWhitespace};
+use std::str::{FromStr, SplitWhitespace, Utf8Error};
 
 use super::HttpError;
 use super::HttpMethod;
 use super::HttpStatus;
+use super::HttpVersion;
+use super::Query;
+use super::TypedHeader;
+use super::limits::Limits;
+use super::uri::normalize_path;
+use super::validation::{self, ParseOptions};
+use super::{HeaderMap, HeaderName, HeaderValue};
 
 use tracing::{debug, trace, warn};
 
-type RequestLine<'a> = (&'a str, &'a str, HttpMethod);
-pub type Headers<'a> = HashMap<Cow<'a, str>, Cow<'a, str>>;
+type RequestLine<'a> = (&'a str, HttpVersion, HttpMethod);
+type Header<'a> = (HeaderName<'a>, HeaderValue<'a>);
 pub type Params<'a> = HashMap<&'a str, &'a str>;
 
-const HEADERS_SEPARATOR: char = ':';
+const HEADERS_SEPARATOR: u8 = b':';
+const QUERY_SEPARATOR: char = '?';
+const CONTENT_LENGTH: &str = "content-length";
+const TRANSFER_ENCODING: &str = "transfer-encoding";
+const CHUNKED: &str = "chunked";
+const CONNECTION: &str = "connection";
+const CLOSE: &str = "close";
+const KEEP_ALIVE: &str = "keep-alive";
 
 #[derive(Debug)]
 pub struct Request<'a> {
     pub method: HttpMethod,
-    pub path: &'a str,
-    pub version: &'a str,
-    pub headers: Headers<'a>,
+    pub path: Cow<'a, str>,
+    pub target: &'a str,
+    pub query: Query<'a>,
+    pub version: HttpVersion,
+    pub headers: HeaderMap<'a>,
     pub params: Params<'a>,
+    pub body: Cow<'a, [u8]>,
 }
 
 impl<'a> Request<'a> {
-    pub fn new(raw_request: &'a str) -> Result<Self, HttpError> {
-        trace!("Starting request parsing");
-        let mut lines: Lines = raw_request.lines();
+    pub fn new(raw_request: &'a [u8]) -> Result<Self, HttpError> {
+        Self::parse(raw_request).map(|(request, _): (Self, usize)| request)
+    }
+
+    pub fn parse(raw_request: &'a [u8]) -> Result<(Self, usize), HttpError> {
+        Self::parse_with(raw_request, &ParseOptions::lenient(), &Limits::default())
+    }
+
+    pub fn parse_with(
+        raw_request: &'a [u8],
+        options: &ParseOptions,
+        limits: &Limits,
+    ) -> Result<(Self, usize), HttpError> {
+        trace!("Starting request parsing (strict: {})", options.strict);
+
+        let head_length: usize = match Self::head_length(raw_request) {
+            Some(head_length) => head_length,
+            None => {
+                limits.check_head_size(raw_request.len())?;
+                warn!("Request head is empty or incomplete");
+                return Err(HttpError::new(HttpStatus::BadRequest, "Request head is empty or incomplete"));
+            }
+        };
+
+        limits.check_head_size(head_length)?;
+
+        if options.strict {
+            validation::validate_line_endings(&raw_request[..head_length])?;
+        }
+
+        let mut lines = raw_request[..head_length]
+            .split(|byte: &u8| *byte == b'\n')
+            .map(|line: &[u8]| line.strip_suffix(b"\r").unwrap_or(line));
 
-        let request_lines: &str = lines.next().ok_or_else(|| {
+        let request_line: &[u8] = lines.next().filter(|line: &&[u8]| !line.is_empty()).ok_or_else(|| {
             warn!("Received empty request line");
             HttpError::new(HttpStatus::BadRequest, "Request line is empty or missing")
         })?;
 
-        let (path, version, method): RequestLine = Self::parse_request_line(request_lines)?;
-        debug!("Parsed request line: {method} {path} {version}");
+        let request_line: &str = str::from_utf8(request_line).map_err(|e: Utf8Error| {
+            warn!("Request line is not valid UTF-8: {e}");
+            HttpError::new(HttpStatus::BadRequest, "Request line contains invalid characters")
+        })?;
+
+        if options.strict {
+            validation::validate_request_line(request_line)?;
+        }
+
+        let (target, version, method): RequestLine = Self::parse_request_line(request_line)?;
+        debug!("Parsed request line: {method} {target} {version}");
+
+        limits.check_uri_length(target.len())?;
+        let (path, query): (Cow<str>, Query) = Self::parse_target(target)?;
+
+        let headers: HeaderMap = lines
+            .take_while(|line: &&[u8]| !line.is_empty())
+            .enumerate()
+            .map(|(i, line): (usize, &[u8])| {
+                limits.check_header_count(i + 1)?;
 
-        let headers: Headers = Self::parse_headers(lines)?;
-        trace!("Parsed {} headers", headers.len());
+                if options.strict {
+                    validation::validate_header_line(line)?;
+                }
+
+                let (name, value): Header = Self::parse_header(line)?;
+                limits.check_header_field(name.as_str(), value.as_bytes())?;
+                Ok((name, value))
+            })
+            .collect::<Result<HeaderMap, HttpError>>()?;
+
+        if options.strict {
+            validation::validate_framing(&headers, version)?;
+        }
+
+        trace!("Parsed {} headers in {head_length} bytes", headers.len());
+
+        let request: Self = Self {
+            headers,
+            path,
+            target,
+            query,
+            version,
+            method,
+            params: HashMap::new(),
+            body: Cow::Borrowed(&[]),
+        };
+
+        Ok((request, head_length))
+    }
+
+    pub fn from_parts(
+        method: HttpMethod,
+        target: &'a str,
+        version: HttpVersion,
+        headers: HeaderMap<'a>,
+        limits: &Limits,
+    ) -> Result<Self, HttpError> {
+        limits.check_uri_length(target.len())?;
+        limits.check_header_count(headers.len())?;
+
+        headers
+            .iter()
+            .try_for_each(|(name, value): (&HeaderName, &HeaderValue)| {
+                limits.check_header_field(name.as_str(), value.as_bytes())
+            })?;
+
+        let (path, query): (Cow<str>, Query) = Self::parse_target(target)?;
+        debug!("Built request from parts: {method} {target} {version}");
 
         Ok(Self {
             headers,
             path,
+            target,
+            query,
             version,
             method,
             params: HashMap::new(),
+            body: Cow::Borrowed(&[]),
         })
     }
 
+    pub fn head_length(raw: &[u8]) -> Option<usize> {
+        raw.iter()
+            .enumerate()
+            .filter(|(_, byte): &(usize, &u8)| **byte == b'\n')
+            .find_map(|(i, _): (usize, &u8)| match raw.get(i + 1..) {
+                Some([b'\n', ..]) => Some(i + 2),
+                Some([b'\r', b'\n', ..]) => Some(i + 3),
+                _ => None,
+            })
+    }
+
+    pub fn header(&self, name: &str) -> Result<Option<&str>, HttpError> {
+        self.headers
+            .get(name)
+            .map(|value: &HeaderValue| value.to_str())
+            .transpose()
+    }
+
+    pub fn typed_header<T>(&self) -> Result<Option<T>, HttpError>
+    where
+        T: TypedHeader,
+    {
+        if !self.headers.contains_key(T::NAME) {
+            return Ok(None);
+        }
+
+        T::decode(self.headers.get_all(T::NAME)).map(Some)
+    }
+
+    pub fn content_length(&self) -> Result<Option<usize>, HttpError> {
+        let Some(value) = self.header(CONTENT_LENGTH)? else {
+            return Ok(None);
+        };
+
+        let length: usize = value.parse::<usize>().map_err(|_| {
+            warn!("Invalid Content-Length header: '{value}'");
+            HttpError::new(HttpStatus::BadRequest, format!("Invalid Content-Length header: \"{value}\""))
+        })?;
+
+        Ok(Some(length))
+    }
+
+    pub fn is_chunked(&self) -> Result<bool, HttpError> {
+        if !self.headers.contains_key(TRANSFER_ENCODING) {
+            return Ok(false);
+        }
+
+        let codings: Vec<&str> = validation::transfer_codings(&self.headers)?;
+
+        if !codings
+            .last()
+            .is_some_and(|coding: &&str| coding.eq_ignore_ascii_case(CHUNKED))
+        {
+            let value: String = codings.join(", ");
+            warn!("Unsupported Transfer-Encoding: '{value}'");
+            return Err(HttpError::new(
+                HttpStatus::BadRequest,
+                format!("Unsupported Transfer-Encoding: \"{value}\""),
+            ));
+        }
+
+        Ok(true)
+    }
+
+    pub fn keep_alive(&self) -> bool {
+        let has_option = |expected: &str| -> bool {
+            self.headers
+                .get_all(CONNECTION)
+                .filter_map(|value: &HeaderValue| value.to_str().ok())
+                .flat_map(|value: &str| value.split(','))
+                .any(|option: &str| option.trim().eq_ignore_ascii_case(expected))
+        };
+
+        if has_option(CLOSE) {
+            return false;
+        }
+
+        has_option(KEEP_ALIVE) || self.version.keep_alive_by_default()
+    }
+
     pub fn set_params(&mut self, raw_params: Vec<(&'a str, &'a str)>) {
         self.params.extend(raw_params);
     }
 
-    fn parse_headers(raw_headers: Lines) -> Result<Headers, HttpError> {
-        raw_headers
-            .take_while(|line: &&str| !line.trim().is_empty())
-            .map(|header: &str| {
-                let values: (&str, &str) = header.split_once(HEADERS_SEPARATOR).ok_or_else(|| {
-                    warn!("Malformed header found: '{header}'");
-                    HttpError::new(HttpStatus::BadRequest, format!("Invalid header format: \"{header}\""))
-                })?;
+    pub fn set_body<T>(&mut self, body: T)
+    where
+        T: Into<Cow<'a, [u8]>>,
+    {
+        self.body = body.into();
+    }
+
+    fn parse_header(line: &'a [u8]) -> Result<Header<'a>, HttpError> {
+        let separator: usize = line
+            .iter()
+            .position(|byte: &u8| *byte == HEADERS_SEPARATOR)
+            .ok_or_else(|| {
+                let header: Cow<str> = String::from_utf8_lossy(line);
+                warn!("Malformed header found: '{header}'");
+                HttpError::new(HttpStatus::BadRequest, format!("Invalid header format: \"{header}\""))
+            })?;
+
+        let key: &str = str::from_utf8(&line[..separator])
+            .map_err(|e: Utf8Error| {
+                warn!("Header name is not valid UTF-8: {e}");
+                HttpError::new(HttpStatus::BadRequest, "Header name contains invalid characters")
+            })?
+            .trim();
+
+        let value: &[u8] = line[separator + 1..].trim_ascii();
+        Ok((HeaderName::from(key), HeaderValue::from(value)))
+    }
 
-                let key: &str = values.0.trim();
-                let value: &str = values.1.trim();
+    fn parse_target(target: &'a str) -> Result<(Cow<'a, str>, Query<'a>), HttpError> {
+        let (path, raw_query): (&str, &str) = target.split_once(QUERY_SEPARATOR).unwrap_or((target, ""));
 
-                let key_cow: Cow<str> = if key.as_bytes().iter().any(|byte: &u8| byte.is_ascii_uppercase()) {
-                    Cow::Owned(key.to_ascii_lowercase())
-                } else {
-                    Cow::Borrowed(key)
-                };
+        let path: Cow<str> = normalize_path(path)?;
+        trace!("Normalized request path: {path}");
 
-                Ok((key_cow, Cow::Borrowed(value)))
-            })
-            .collect::<Result<Headers, HttpError>>()
+        let query: Query = Query::new(raw_query)?;
+        trace!("Parsed {} query parameters", query.len());
+
+        Ok((path, query))
     }
 
     fn parse_request_line(raw_request_line: &str) -> Result<RequestLine<'_>, HttpError> {
@@ -83,69 +294,83 @@ impl<'a> Request<'a> {
             HttpError::new(HttpStatus::BadRequest, "Request line missing HTTP Method")
         })?;
 
-        let path: &str = parts.next().ok_or_else(|| {
+        let target: &str = parts.next().ok_or_else(|| {
             warn!("Missing URI Path in request line");
             HttpError::new(HttpStatus::BadRequest, "Request line missing URI Path")
         })?;
 
-        let version: &str = parts.next().ok_or_else(|| {
+        let version_str: &str = parts.next().ok_or_else(|| {
             warn!("Missing HTTP Version in request line");
             HttpError::new(HttpStatus::BadRequest, "Request line missing HTTP Version")
         })?;
 
+        let version: HttpVersion = HttpVersion::from_str(version_str).inspect_err(|_| {
+            warn!("Invalid or unsupported HTTP Version: '{version_str}'");
+        })?;
+
         let method: HttpMethod = HttpMethod::from_str(method_str).inspect_err(|_| {
             warn!("Invalid HTTP Method: '{method_str}'");
         })?;
 
-        Ok((path, version, method))
+        Ok((target, version, method))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::typed_headers::{Accept, Authorization, ContentType, Host};
 
     #[test]
     fn test_parse_valid_simple_request() {
-        let raw: &str = "GET /index.html HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        let raw: &[u8] = b"GET /index.html HTTP/1.1\r\nHost: localhost\r\n\r\n";
         let req: Request = Request::new(raw).expect("Should parse valid request");
 
         assert_eq!(req.method, HttpMethod::GET);
         assert_eq!(req.path, "/index.html");
-        assert_eq!(req.version, "HTTP/1.1");
-        assert_eq!(req.headers.get("host").map(|v| v.as_ref()), Some("localhost"));
+        assert_eq!(req.version, HttpVersion::Http11);
+        assert_eq!(req.header("host").unwrap(), Some("localhost"));
     }
 
     #[test]
     fn test_parse_headers_case_insensitivity() {
-        let raw: &str = "POST /submit HTTP/1.1\r\nCONTENT-TYPE: application/json\r\nX-Custom-Header: value\r\n\r\n";
+        let raw: &[u8] = b"POST /submit HTTP/1.1\r\nCONTENT-TYPE: application/json\r\nX-Custom-Header: value\r\n\r\n";
         let req: Request = Request::new(raw).expect("Should parse headers");
 
         assert!(req.headers.contains_key("content-type"));
         assert!(req.headers.contains_key("x-custom-header"));
 
-        match req
-            .headers
-            .keys()
-            .find(|k: &&Cow<str>| k.as_ref() == "content-type")
-            .unwrap()
-        {
-            Cow::Owned(_) => {}
-            _ => panic!("Expected Owned Cow for converted lowercase key"),
-        }
+        let names: Vec<&str> = req.headers.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["content-type", "x-custom-header"]);
+    }
+
+    #[test]
+    fn test_parse_repeated_headers() {
+        let raw: &[u8] =
+            b"GET / HTTP/1.1\r\nAccept: text/html\r\nCookie: a=1\r\naccept: application/json\r\nCookie: b=2\r\n\r\n";
+        let req: Request = Request::new(raw).unwrap();
+
+        assert_eq!(req.headers.len(), 4);
+        assert_eq!(req.header("accept").unwrap(), Some("text/html"));
+
+        let accept: Vec<&HeaderValue> = req.headers.get_all("Accept").collect();
+        assert_eq!(accept, vec!["text/html", "application/json"]);
+
+        let cookies: Vec<&HeaderValue> = req.headers.get_all("cookie").collect();
+        assert_eq!(cookies, vec!["a=1", "b=2"]);
     }
 
     #[test]
     fn test_parse_headers_trim_whitespace() {
-        let raw: &str = "GET / HTTP/1.1\r\nKey:    value with spaces    \r\n\r\n";
+        let raw: &[u8] = b"GET / HTTP/1.1\r\nKey:    value with spaces    \r\n\r\n";
         let req: Request = Request::new(raw).unwrap();
 
-        assert_eq!(req.headers.get("key").map(|v| v.as_ref()), Some("value with spaces"));
+        assert_eq!(req.header("key").unwrap(), Some("value with spaces"));
     }
 
     #[test]
     fn test_request_empty_string() {
-        let raw: &str = "";
+        let raw: &[u8] = b"";
         let result: Result<Request, HttpError> = Request::new(raw);
 
         assert!(result.is_err());
@@ -154,25 +379,69 @@ mod tests {
 
     #[test]
     fn test_request_invalid_method() {
-        let raw: &str = "INVALIDMETHOD /path HTTP/1.1\r\n\r\n";
+        let raw: &[u8] = b"INVALID(METHOD) /path HTTP/1.1\r\n\r\n";
         let result: Result<Request, HttpError> = Request::new(raw);
 
         assert!(result.is_err());
         assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest);
     }
 
+    #[test]
+    fn test_request_extension_method() {
+        let propfind: Request = Request::new(b"PROPFIND /files HTTP/1.1\r\nDepth: 1\r\n\r\n").unwrap();
+        let purge: Request = Request::new(b"PURGE /cache HTTP/1.1\r\n\r\n").unwrap();
+
+        assert_eq!(propfind.method, HttpMethod::PROPFIND);
+        assert!(matches!(&purge.method, HttpMethod::Extension(method) if method.as_str() == "PURGE"));
+    }
+
     #[test]
     fn test_request_missing_version() {
-        let raw: &str = "GET /path\r\n\r\n";
+        let raw: &[u8] = b"GET /path\r\n\r\n";
         let result: Result<Request, HttpError> = Request::new(raw);
 
         assert!(result.is_err());
         assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest);
     }
 
+    #[test]
+    fn test_request_unsupported_version() {
+        let raw: &[u8] = b"GET /path HTTP/2.0\r\n\r\n";
+        let result: Result<Request, HttpError> = Request::new(raw);
+
+        assert_eq!(result.unwrap_err().status, HttpStatus::HttpVersionNotSupported);
+    }
+
+    #[test]
+    fn test_request_keep_alive() {
+        let http11: Request = Request::new(b"GET / HTTP/1.1\r\n\r\n").unwrap();
+        let http11_close: Request = Request::new(b"GET / HTTP/1.1\r\nConnection: Close\r\n\r\n").unwrap();
+        let http10: Request = Request::new(b"GET / HTTP/1.0\r\n\r\n").unwrap();
+        let http10_keep_alive: Request = Request::new(b"GET / HTTP/1.0\r\nConnection: Keep-Alive\r\n\r\n").unwrap();
+
+        assert_eq!(http10.version, HttpVersion::Http10);
+        assert!(http11.keep_alive());
+        assert!(!http11_close.keep_alive());
+        assert!(!http10.keep_alive());
+        assert!(http10_keep_alive.keep_alive());
+    }
+
+    #[test]
+    fn test_request_close_takes_precedence() {
+        for raw in [
+            &b"GET / HTTP/1.1\r\nConnection: keep-alive, close\r\n\r\n"[..],
+            b"GET / HTTP/1.1\r\nConnection: close, keep-alive\r\n\r\n",
+            b"GET / HTTP/1.1\r\nConnection: keep-alive\r\nConnection: close\r\n\r\n",
+            b"GET / HTTP/1.0\r\nConnection: Keep-Alive, Close\r\n\r\n",
+        ] {
+            let request: Request = Request::new(raw).unwrap();
+            assert!(!request.keep_alive(), "Kept alive: {:?}", String::from_utf8_lossy(raw));
+        }
+    }
+
     #[test]
     fn test_header_missing_colon() {
-        let raw: &str = "GET / HTTP/1.1\r\nInvalidHeader\r\n\r\n";
+        let raw: &[u8] = b"GET / HTTP/1.1\r\nInvalidHeader\r\n\r\n";
         let result: Result<Request, HttpError> = Request::new(raw);
 
         assert!(result.is_err());
@@ -181,7 +450,7 @@ mod tests {
 
     #[test]
     fn test_set_params() {
-        let raw: &str = "GET /store/123 HTTP/1.1\r\n\r\n";
+        let raw: &[u8] = b"GET /store/123 HTTP/1.1\r\n\r\n";
         let mut req: Request = Request::new(raw).unwrap();
 
         assert!(req.params.is_empty());
@@ -193,4 +462,202 @@ mod tests {
         assert_eq!(req.params.get("store_id"), Some(&"123"));
         assert_eq!(req.params.get("filter"), Some(&"active"));
     }
+
+    #[test]
+    fn test_parse_query_string() {
+        let raw: &[u8] = b"GET /users?id=1&tag=a&tag=b%20c HTTP/1.1\r\n\r\n";
+        let req: Request = Request::new(raw).unwrap();
+
+        assert_eq!(req.path, "/users");
+        assert_eq!(req.query.as_str(), "id=1&tag=a&tag=b%20c");
+        assert_eq!(req.query.get("id"), Some("1"));
+        assert_eq!(req.query.get_all("tag").collect::<Vec<&str>>(), vec!["a", "b c"]);
+    }
+
+    #[test]
+    fn test_parse_without_query_string() {
+        let raw: &[u8] = b"GET /users HTTP/1.1\r\n\r\n";
+        let req: Request = Request::new(raw).unwrap();
+
+        assert_eq!(req.path, "/users");
+        assert!(req.query.is_empty());
+    }
+
+    #[test]
+    fn test_parse_normalizes_path() {
+        let raw: &[u8] = b"GET /files/./x/../a%20b?name=%2E%2E HTTP/1.1\r\n\r\n";
+        let req: Request = Request::new(raw).unwrap();
+
+        assert_eq!(req.path, "/files/a b");
+        assert_eq!(req.query.get("name"), Some(".."));
+    }
+
+    #[test]
+    fn test_invalid_path_encoding() {
+        let raw: &[u8] = b"GET /files/%zz HTTP/1.1\r\n\r\n";
+        let result: Result<Request, HttpError> = Request::new(raw);
+
+        assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest);
+    }
+
+    #[test]
+    fn test_invalid_query_string_encoding() {
+        let raw: &[u8] = b"GET /users?id=%G1 HTTP/1.1\r\n\r\n";
+        let result: Result<Request, HttpError> = Request::new(raw);
+
+        assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest);
+    }
+
+    #[test]
+    fn test_typed_header() {
+        let raw: &[u8] = b"GET / HTTP/1.1\r\nHost: localhost:8080\r\nAccept: text/html\r\nAccept: */*;q=0.1\r\n\r\n";
+        let req: Request = Request::new(raw).unwrap();
+
+        let host: Host = req.typed_header::<Host>().unwrap().unwrap();
+        assert_eq!(host.hostname(), "localhost");
+        assert_eq!(host.port(), Some(8080));
+
+        let accept: Accept = req.typed_header::<Accept>().unwrap().unwrap();
+        assert_eq!(accept.ranges().len(), 2);
+
+        assert!(req.typed_header::<Authorization>().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_typed_header_invalid_value() {
+        let raw: &[u8] = b"POST / HTTP/1.1\r\nContent-Type: not-a-mime\r\n\r\n";
+        let req: Request = Request::new(raw).unwrap();
+        let result: Result<Option<ContentType>, HttpError> = req.typed_header::<ContentType>();
+
+        assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest);
+    }
+
+    #[test]
+    fn test_head_length() {
+        let raw: &[u8] = b"POST /submit HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello";
+        assert_eq!(Request::head_length(raw), Some(raw.len() - 5));
+
+        let bare_lf: &[u8] = b"GET / HTTP/1.1\nHost: localhost\n\nbody";
+        assert_eq!(Request::head_length(bare_lf), Some(bare_lf.len() - 4));
+
+        let incomplete: &[u8] = b"GET / HTTP/1.1\r\nHost: localhost\r\n";
+        assert_eq!(Request::head_length(incomplete), None);
+    }
+
+    #[test]
+    fn test_content_length() {
+        let raw: &[u8] = b"POST /submit HTTP/1.1\r\nContent-Length: 42\r\n\r\n";
+        let req: Request = Request::new(raw).unwrap();
+        assert_eq!(req.content_length().unwrap(), Some(42));
+
+        let raw: &[u8] = b"GET / HTTP/1.1\r\n\r\n";
+        let req: Request = Request::new(raw).unwrap();
+        assert_eq!(req.content_length().unwrap(), None);
+    }
+
+    #[test]
+    fn test_invalid_content_length() {
+        let raw: &[u8] = b"POST /submit HTTP/1.1\r\nContent-Length: -1\r\n\r\n";
+        let req: Request = Request::new(raw).unwrap();
+        let result: Result<Option<usize>, HttpError> = req.content_length();
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest);
+    }
+
+    #[test]
+    fn test_is_chunked() {
+        let raw: &[u8] = b"POST /upload HTTP/1.1\r\nTransfer-Encoding: gzip, Chunked\r\n\r\n";
+        let req: Request = Request::new(raw).unwrap();
+        assert!(req.is_chunked().unwrap());
+
+        let raw: &[u8] = b"POST /upload HTTP/1.1\r\nContent-Length: 3\r\n\r\n";
+        let req: Request = Request::new(raw).unwrap();
+        assert!(!req.is_chunked().unwrap());
+    }
+
+    #[test]
+    fn test_chunked_not_final_coding() {
+        let raw: &[u8] = b"POST /upload HTTP/1.1\r\nTransfer-Encoding: chunked, gzip\r\n\r\n";
+        let req: Request = Request::new(raw).unwrap();
+        let result: Result<bool, HttpError> = req.is_chunked();
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest);
+
+        let raw: &[u8] = b"POST /upload HTTP/1.1\r\nTransfer-Encoding: chunked\r\nTransfer-Encoding: gzip\r\n\r\n";
+        let req: Request = Request::new(raw).unwrap();
+        assert!(req.is_chunked().is_err());
+    }
+
+    #[test]
+    fn test_set_body() {
+        let raw: &[u8] = b"POST /submit HTTP/1.1\r\nContent-Length: 5\r\n\r\n";
+        let mut req: Request = Request::new(raw).unwrap();
+
+        assert!(req.body.is_empty());
+
+        req.set_body(b"hello".to_vec());
+        assert_eq!(req.body.as_ref(), b"hello");
+    }
+
+    #[test]
+    fn test_parse_reports_head_length_and_leaves_body() {
+        let raw: &[u8] = b"POST /upload HTTP/1.1\r\nContent-Length: 4\r\n\r\n\x00\xff\xfe\x80GET / HTTP/1.1\r\n\r\n";
+        let (req, head_length): (Request, usize) = Request::parse(raw).unwrap();
+
+        assert_eq!(req.content_length().unwrap(), Some(4));
+        assert_eq!(&raw[head_length..head_length + 4], b"\x00\xff\xfe\x80");
+        assert_eq!(&raw[head_length + 4..], b"GET / HTTP/1.1\r\n\r\n");
+    }
+
+    #[test]
+    fn test_parse_obs_text_header_value() {
+        let raw: &[u8] = b"GET / HTTP/1.1\r\nX-Legacy: caf\xe9\r\nHost: localhost\r\n\r\n";
+        let req: Request = Request::new(raw).expect("Should accept obs-text header values");
+
+        assert_eq!(req.headers.get("x-legacy").unwrap().as_bytes(), b"caf\xe9");
+        assert_eq!(req.header("x-legacy").unwrap_err().status, HttpStatus::BadRequest);
+        assert_eq!(req.header("host").unwrap(), Some("localhost"));
+    }
+
+    #[test]
+    fn test_parse_incomplete_head() {
+        let raw: &[u8] = b"GET / HTTP/1.1\r\nHost: localhost\r\n";
+        let result: Result<(Request, usize), HttpError> = Request::parse(raw);
+
+        assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest);
+    }
+
+    #[test]
+    fn test_parse_invalid_request_line_bytes() {
+        let raw: &[u8] = b"GET /\xff HTTP/1.1\r\n\r\n";
+        let result: Result<Request, HttpError> = Request::new(raw);
+
+        assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest);
+    }
+
+    #[test]
+    fn test_from_parts() {
+        let mut headers: HeaderMap = HeaderMap::new();
+        headers.append("host", "localhost");
+
+        let limits: Limits = Limits::default();
+        let req: Request =
+            Request::from_parts(HttpMethod::GET, "/a/../users?id=7", HttpVersion::Http2, headers, &limits).unwrap();
+
+        assert_eq!(req.path, "/users");
+        assert_eq!(req.query.get("id"), Some("7"));
+        assert_eq!(req.version, HttpVersion::Http2);
+        assert_eq!(req.header("host").unwrap(), Some("localhost"));
+
+        let limits: Limits = Limits {
+            max_uri_length: 4,
+            ..Limits::default()
+        };
+        let error: HttpError =
+            Request::from_parts(HttpMethod::GET, "/users", HttpVersion::Http2, HeaderMap::new(), &limits).unwrap_err();
+
+        assert_eq!(error.status, HttpStatus::UriTooLong);
+    }
 }
@@ -3,16 +3,23 @@ use std::{
     io::{Cursor, IoSlice, Write},
 };
 
-use super::{HttpError, HttpStatus};
+use super::upgrade::{OnUpgrade, Upgraded};
+use super::{HeaderMap, HeaderName, HeaderValue, HttpError, HttpStatus, HttpVersion, TypedHeader};
 use serde::Serialize;
-use tokio::{io::AsyncWriteExt, net::TcpStream};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 const BUFFER_SIZE: usize = 1024;
+const CONNECTION: &str = "connection";
+const TRANSFER_ENCODING: &str = "transfer-encoding";
+const CLOSE: &str = "close";
 
 pub struct Response<'a> {
     status: HttpStatus,
     body: Option<Cow<'a, str>>,
-    headers: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    headers: HeaderMap<'a>,
+    version: HttpVersion,
+    keep_alive: bool,
+    upgrade: Option<OnUpgrade>,
 }
 
 impl<'a> Response<'a> {
@@ -20,7 +27,10 @@ impl<'a> Response<'a> {
         Self {
             status,
             body: None,
-            headers: Vec::new(),
+            headers: HeaderMap::new(),
+            version: HttpVersion::default(),
+            keep_alive: true,
+            upgrade: None,
         }
     }
 
@@ -34,13 +44,65 @@ impl<'a> Response<'a> {
 
     pub fn header<T, K>(mut self, key: T, value: K) -> Self
     where
-        T: Into<Cow<'a, str>>,
-        K: Into<Cow<'a, str>>,
+        T: Into<HeaderName<'a>>,
+        K: Into<HeaderValue<'a>>,
+    {
+        self.headers.append(key, value);
+        self
+    }
+
+    pub fn typed_header<T>(mut self, header: T) -> Self
+    where
+        T: TypedHeader,
+    {
+        self.headers.insert(T::NAME, header.encode());
+        self
+    }
+
+    pub fn on_upgrade<F, Fut>(mut self, callback: F) -> Self
+    where
+        F: FnOnce(Upgraded) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
     {
-        self.headers.push((key.into(), value.into()));
+        self.upgrade = Some(Box::new(move |io: Upgraded| Box::pin(callback(io))));
         self
     }
 
+    pub fn take_upgrade(&mut self) -> Option<OnUpgrade> {
+        self.upgrade.take()
+    }
+
+    pub fn status(&self) -> HttpStatus {
+        self.status
+    }
+
+    pub fn headers(&self) -> &HeaderMap<'a> {
+        &self.headers
+    }
+
+    pub fn body_bytes(&self) -> &[u8] {
+        self.body.as_deref().map(str::as_bytes).unwrap_or_default()
+    }
+
+    pub fn set_version(&mut self, version: HttpVersion) {
+        self.version = version;
+    }
+
+    pub fn set_keep_alive(&mut self, keep_alive: bool) {
+        self.keep_alive = keep_alive;
+    }
+
+    pub fn keep_alive(&self) -> bool {
+        let closes: bool = self
+            .headers
+            .get_all(CONNECTION)
+            .filter_map(|value: &HeaderValue| value.to_str().ok())
+            .flat_map(|value: &str| value.split(','))
+            .any(|option: &str| option.trim().eq_ignore_ascii_case(CLOSE));
+
+        self.keep_alive && !closes
+    }
+
     pub fn text<T>(self, text: T) -> Self
     where
         T: Into<Cow<'a, str>>,
@@ -65,17 +127,41 @@ impl<'a> Response<'a> {
     fn write_head_to_buffer(&self, buffer: &mut [u8]) -> Result<usize, HttpError> {
         let mut cursor: Cursor<&mut [u8]> = Cursor::new(buffer);
 
-        write!(cursor, "HTTP/1.1 {} {}\r\n", u16::from(self.status), self.status)
+        write!(cursor, "{} {} {}\r\n", self.version, u16::from(self.status), self.status)
             .map_err(|_| HttpError::new(HttpStatus::InternalServerError, "Headers too long for buffer"))?;
 
-        for (key, value) in &self.headers {
-            write!(cursor, "{key}: {value}\r\n")
+        let closing: bool = !self.keep_alive && !self.status.is_informational();
+        let headers = self.headers.iter().filter(|(key, _): &(&HeaderName, &HeaderValue)| {
+            *key != TRANSFER_ENCODING && !(closing && *key == CONNECTION)
+        });
+
+        for (key, value) in headers {
+            write!(cursor, "{}: ", key.canonical())
+                .and_then(|_| Write::write_all(&mut cursor, value.as_bytes()))
+                .and_then(|_| Write::write_all(&mut cursor, b"\r\n"))
                 .map_err(|_| HttpError::new(HttpStatus::InternalServerError, "Headers too long for buffer"))?;
         }
 
-        let content_length: usize = self.body.as_ref().map(|b: &Cow<str>| b.len()).unwrap_or(0);
-        write!(cursor, "Content-Length: {content_length}\r\n\r\n")
-            .map_err(|_| HttpError::new(HttpStatus::InternalServerError, "Headers too long for buffer"))?;
+        if closing || !self.headers.contains_key(CONNECTION) {
+            let connection: Option<&str> = match (self.keep_alive, self.version) {
+                (false, _) => Some("close"),
+                (true, HttpVersion::Http10) => Some("keep-alive"),
+                (true, HttpVersion::Http11 | HttpVersion::Http2) => None,
+            };
+
+            if let Some(connection) = connection {
+                write!(cursor, "Connection: {connection}\r\n")
+                    .map_err(|_| HttpError::new(HttpStatus::InternalServerError, "Headers too long for buffer"))?;
+            }
+        }
+
+        if self.status.is_informational() {
+            write!(cursor, "\r\n")
+        } else {
+            let content_length: usize = self.body.as_ref().map(|b: &Cow<str>| b.len()).unwrap_or(0);
+            write!(cursor, "Content-Length: {content_length}\r\n\r\n")
+        }
+        .map_err(|_| HttpError::new(HttpStatus::InternalServerError, "Headers too long for buffer"))?;
 
         let bytes_written: usize = usize::try_from(cursor.position())
             .map_err(|_| HttpError::new(HttpStatus::InternalServerError, "Header size calculation overflow"))?;
@@ -83,16 +169,32 @@ impl<'a> Response<'a> {
         Ok(bytes_written)
     }
 
-    pub async fn send(&self, stream: &mut TcpStream) -> Result<(), HttpError> {
+    pub async fn send<W>(&self, stream: &mut W) -> Result<(), HttpError>
+    where
+        W: AsyncWrite + Unpin,
+    {
         let mut head_buffer: [u8; BUFFER_SIZE] = [0; BUFFER_SIZE];
         let head_length: usize = self.write_head_to_buffer(&mut head_buffer)?;
         let head_slice: &[u8] = &head_buffer[..head_length];
 
         if let Some(body) = &self.body {
-            stream
-                .write_vectored(&[IoSlice::new(head_slice), IoSlice::new(body.as_bytes())])
-                .await
-                .map_err(|_| HttpError::new(HttpStatus::InternalServerError, "Failed to write vectored response"))?;
+            let mut slices: [IoSlice; 2] = [IoSlice::new(head_slice), IoSlice::new(body.as_bytes())];
+            let mut remaining: &mut [IoSlice] = &mut slices;
+
+            while !remaining.is_empty() {
+                let bytes_written: usize = stream.write_vectored(remaining).await.map_err(|_| {
+                    HttpError::new(HttpStatus::InternalServerError, "Failed to write vectored response")
+                })?;
+
+                if bytes_written == 0 {
+                    return Err(HttpError::new(
+                        HttpStatus::InternalServerError,
+                        "Failed to write vectored response",
+                    ));
+                }
+
+                IoSlice::advance_slices(&mut remaining, bytes_written);
+            }
         } else {
             stream
                 .write_all(head_slice)
@@ -128,6 +230,7 @@ impl<'a> From<HttpError> for Response<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::typed_headers::{CacheControl, CacheDirective, ContentType, Location};
 
     #[test]
     fn test_response_into_response() {
@@ -147,6 +250,112 @@ mod tests {
         assert_eq!(response.body.unwrap(), "NOT_FOUND");
     }
 
+    #[test]
+    fn test_response_repeated_headers() {
+        let response: Response = Response::new(HttpStatus::Ok)
+            .header("Set-Cookie", "a=1")
+            .header("Set-Cookie", "b=2")
+            .text("OK");
+
+        let cookies: Vec<&HeaderValue> = response.headers().get_all("set-cookie").collect();
+        assert_eq!(cookies, vec!["a=1", "b=2"]);
+        assert_eq!(response.headers().get("Content-Type").unwrap(), "text/plain");
+    }
+
+    #[test]
+    fn test_response_typed_header() {
+        let response: Response = Response::new(HttpStatus::Created)
+            .typed_header(ContentType::json())
+            .typed_header(Location::new("/users/1").unwrap())
+            .typed_header(CacheControl::new().with(CacheDirective::NoStore));
+
+        assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+        assert_eq!(response.headers().get("location").unwrap(), "/users/1");
+        assert_eq!(response.headers().get("cache-control").unwrap(), "no-store");
+    }
+
+    #[test]
+    fn test_write_head_to_buffer() {
+        let response: Response = Response::new(HttpStatus::Ok)
+            .header("X-Request-Id", "42")
+            .header("Vary", "Accept")
+            .header("Vary", "Origin")
+            .body("OK");
+
+        let mut buffer: [u8; BUFFER_SIZE] = [0; BUFFER_SIZE];
+        let length: usize = response.write_head_to_buffer(&mut buffer).unwrap();
+
+        assert_eq!(
+            &buffer[..length],
+            b"HTTP/1.1 200 OK\r\nx-request-id: 42\r\nVary: Accept\r\nVary: Origin\r\nContent-Length: 2\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn test_write_head_for_http_1_0() {
+        let mut response: Response = Response::new(HttpStatus::Ok)
+            .header("Transfer-Encoding", "chunked")
+            .body("OK");
+        response.set_version(HttpVersion::Http10);
+        response.set_keep_alive(false);
+
+        let mut buffer: [u8; BUFFER_SIZE] = [0; BUFFER_SIZE];
+        let length: usize = response.write_head_to_buffer(&mut buffer).unwrap();
+
+        assert_eq!(
+            &buffer[..length],
+            b"HTTP/1.0 200 OK\r\nConnection: close\r\nContent-Length: 2\r\n\r\n"
+        );
+
+        response.set_keep_alive(true);
+        let length: usize = response.write_head_to_buffer(&mut buffer).unwrap();
+
+        assert_eq!(
+            &buffer[..length],
+            b"HTTP/1.0 200 OK\r\nConnection: keep-alive\r\nContent-Length: 2\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn test_write_head_never_combines_transfer_encoding_and_content_length() {
+        let response: Response = Response::new(HttpStatus::Ok)
+            .header("Transfer-Encoding", "chunked")
+            .body("OK");
+
+        let mut buffer: [u8; BUFFER_SIZE] = [0; BUFFER_SIZE];
+        let length: usize = response.write_head_to_buffer(&mut buffer).unwrap();
+
+        assert_eq!(&buffer[..length], b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n");
+    }
+
+    #[test]
+    fn test_write_head_replaces_connection_header_when_closing() {
+        let mut response: Response = Response::new(HttpStatus::Ok)
+            .header("Connection", "keep-alive")
+            .body("OK");
+        response.set_keep_alive(false);
+
+        let mut buffer: [u8; BUFFER_SIZE] = [0; BUFFER_SIZE];
+        let length: usize = response.write_head_to_buffer(&mut buffer).unwrap();
+
+        assert_eq!(
+            &buffer[..length],
+            b"HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: 2\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn test_response_connection_close_header() {
+        let response: Response = Response::new(HttpStatus::Ok).header("Connection", "close");
+        assert!(!response.keep_alive());
+
+        let mut response: Response = Response::new(HttpStatus::Ok);
+        assert!(response.keep_alive());
+
+        response.set_keep_alive(false);
+        assert!(!response.keep_alive());
+    }
+
     #[test]
     fn test_json_response_success() {
         let user: serde_json::Value = serde_json::json!({ "name": "John Doe", "age": 18 });
@@ -69,6 +69,12 @@ pub enum HttpStatus {
     NetworkAuthenticationRequired = 511,
 }
 
+impl HttpStatus {
+    pub fn is_informational(&self) -> bool {
+        (100..200).contains(&u16::from(*self))
+    }
+}
+
 impl From<HttpStatus> for u16 {
     fn from(status: HttpStatus) -> u16 {
         status as u16
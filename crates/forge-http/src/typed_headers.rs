@@ -0,0 +1,1000 @@
+use std::cmp::Reverse;
+use std::fmt::{self, Write};
+
+use super::headers::is_token;
+use super::{HeaderValue, HttpError, HttpStatus};
+use tracing::warn;
+
+type Params = Vec<(String, String)>;
+
+const LIST_SEPARATOR: char = ',';
+const PARAMS_SEPARATOR: char = ';';
+const QUOTE: char = '"';
+const MAX_QUALITY: u16 = 1000;
+
+pub trait TypedHeader: Sized {
+    const NAME: &'static str;
+
+    fn decode<'v, 'h: 'v, I>(values: I) -> Result<Self, HttpError>
+    where
+        I: Iterator<Item = &'v HeaderValue<'h>>;
+
+    fn encode(&self) -> HeaderValue<'static>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentType {
+    media_type: String,
+    params: Params,
+}
+
+impl ContentType {
+    pub fn new(value: &str) -> Result<Self, HttpError> {
+        let (media_type, params): (String, Params) = parse_media_type(value).ok_or_else(|| invalid(Self::NAME))?;
+
+        Ok(Self { media_type, params })
+    }
+
+    pub fn json() -> Self {
+        Self::from_static("application/json")
+    }
+
+    pub fn text() -> Self {
+        Self::from_static("text/plain").with_utf8_charset()
+    }
+
+    pub fn html() -> Self {
+        Self::from_static("text/html").with_utf8_charset()
+    }
+
+    pub fn form() -> Self {
+        Self::from_static("application/x-www-form-urlencoded")
+    }
+
+    pub fn octet_stream() -> Self {
+        Self::from_static("application/octet-stream")
+    }
+
+    pub fn with_param(mut self, name: &str, value: &str) -> Result<Self, HttpError> {
+        if !is_token(name) || !is_field_value(value) {
+            return Err(invalid(Self::NAME));
+        }
+
+        self.params.push((name.to_ascii_lowercase(), value.into()));
+        Ok(self)
+    }
+
+    pub fn media_type(&self) -> &str {
+        &self.media_type
+    }
+
+    pub fn param(&self, name: &str) -> Option<&str> {
+        find_param(&self.params, name)
+    }
+
+    pub fn charset(&self) -> Option<&str> {
+        self.param("charset")
+    }
+
+    fn from_static(media_type: &'static str) -> Self {
+        Self {
+            media_type: media_type.into(),
+            params: Vec::new(),
+        }
+    }
+
+    fn with_utf8_charset(mut self) -> Self {
+        self.params.push(("charset".into(), "utf-8".into()));
+        self
+    }
+}
+
+impl TypedHeader for ContentType {
+    const NAME: &'static str = "content-type";
+
+    fn decode<'v, 'h: 'v, I>(values: I) -> Result<Self, HttpError>
+    where
+        I: Iterator<Item = &'v HeaderValue<'h>>,
+    {
+        Self::new(single(Self::NAME, values)?)
+    }
+
+    fn encode(&self) -> HeaderValue<'static> {
+        fmt_media_type(&self.media_type, &self.params).into()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentLength(pub u64);
+
+impl TypedHeader for ContentLength {
+    const NAME: &'static str = "content-length";
+
+    fn decode<'v, 'h: 'v, I>(values: I) -> Result<Self, HttpError>
+    where
+        I: Iterator<Item = &'v HeaderValue<'h>>,
+    {
+        let value: &str = single(Self::NAME, values)?;
+
+        if value.is_empty() || !value.bytes().all(|byte: u8| byte.is_ascii_digit()) {
+            return Err(invalid(Self::NAME));
+        }
+
+        value.parse::<u64>().map(ContentLength).map_err(|_| invalid(Self::NAME))
+    }
+
+    fn encode(&self) -> HeaderValue<'static> {
+        self.0.to_string().into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaRange {
+    media_type: String,
+    params: Params,
+    quality: u16,
+}
+
+impl MediaRange {
+    pub fn new(media_type: &str) -> Result<Self, HttpError> {
+        let (media_type, params): (String, Params) =
+            parse_media_type(media_type).ok_or_else(|| invalid(Accept::NAME))?;
+
+        Ok(Self {
+            media_type,
+            params,
+            quality: MAX_QUALITY,
+        })
+    }
+
+    pub fn with_quality(mut self, quality: f32) -> Self {
+        self.quality = (quality.clamp(0.0, 1.0) * f32::from(MAX_QUALITY)).round() as u16;
+        self
+    }
+
+    pub fn media_type(&self) -> &str {
+        &self.media_type
+    }
+
+    pub fn param(&self, name: &str) -> Option<&str> {
+        find_param(&self.params, name)
+    }
+
+    pub fn quality(&self) -> f32 {
+        f32::from(self.quality) / f32::from(MAX_QUALITY)
+    }
+
+    pub fn matches(&self, media_type: &str) -> bool {
+        let Some((kind, subtype)) = media_type.split_once('/') else {
+            return false;
+        };
+
+        match self.media_type.split_once('/') {
+            Some(("*", "*")) => true,
+            Some((range_kind, "*")) => range_kind.eq_ignore_ascii_case(kind),
+            Some((range_kind, range_subtype)) => {
+                range_kind.eq_ignore_ascii_case(kind) && range_subtype.eq_ignore_ascii_case(subtype)
+            }
+            None => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accept(Vec<MediaRange>);
+
+impl Accept {
+    pub fn new(ranges: Vec<MediaRange>) -> Self {
+        Self(ranges)
+    }
+
+    pub fn ranges(&self) -> &[MediaRange] {
+        &self.0
+    }
+
+    pub fn preferred(&self) -> Vec<&MediaRange> {
+        let mut ranges: Vec<&MediaRange> = self.0.iter().filter(|range: &&MediaRange| range.quality > 0).collect();
+        ranges.sort_by_key(|range: &&MediaRange| Reverse(range.quality));
+        ranges
+    }
+
+    pub fn accepts(&self, media_type: &str) -> bool {
+        self.0.is_empty()
+            || self
+                .preferred()
+                .iter()
+                .any(|range: &&MediaRange| range.matches(media_type))
+    }
+}
+
+impl TypedHeader for Accept {
+    const NAME: &'static str = "accept";
+
+    fn decode<'v, 'h: 'v, I>(values: I) -> Result<Self, HttpError>
+    where
+        I: Iterator<Item = &'v HeaderValue<'h>>,
+    {
+        let ranges: Vec<MediaRange> = list(Self::NAME, values)?
+            .into_iter()
+            .map(|item: &str| {
+                let (media_type, mut params): (String, Params) =
+                    parse_media_type(item).ok_or_else(|| invalid(Self::NAME))?;
+
+                let quality: u16 = match params.iter().position(|(name, _): &(String, String)| name == "q") {
+                    Some(index) => parse_quality(&params.remove(index).1).ok_or_else(|| invalid(Self::NAME))?,
+                    None => MAX_QUALITY,
+                };
+
+                Ok(MediaRange {
+                    media_type,
+                    params,
+                    quality,
+                })
+            })
+            .collect::<Result<Vec<MediaRange>, HttpError>>()?;
+
+        Ok(Self(ranges))
+    }
+
+    fn encode(&self) -> HeaderValue<'static> {
+        let ranges: Vec<String> = self
+            .0
+            .iter()
+            .map(|range: &MediaRange| {
+                let mut encoded: String = fmt_media_type(&range.media_type, &range.params);
+
+                if range.quality < MAX_QUALITY {
+                    let _ = write!(encoded, ";q={}", range.quality());
+                }
+
+                encoded
+            })
+            .collect();
+
+        ranges.join(", ").into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Authorization {
+    scheme: String,
+    credentials: String,
+}
+
+impl Authorization {
+    pub fn new(scheme: &str, credentials: &str) -> Result<Self, HttpError> {
+        if !is_token(scheme) || !is_field_value(credentials) {
+            return Err(invalid(Self::NAME));
+        }
+
+        Ok(Self {
+            scheme: scheme.into(),
+            credentials: credentials.into(),
+        })
+    }
+
+    pub fn bearer(token: &str) -> Result<Self, HttpError> {
+        Self::new("Bearer", token)
+    }
+
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    pub fn credentials(&self) -> &str {
+        &self.credentials
+    }
+
+    pub fn bearer_token(&self) -> Option<&str> {
+        self.scheme
+            .eq_ignore_ascii_case("bearer")
+            .then_some(self.credentials.as_str())
+    }
+}
+
+impl TypedHeader for Authorization {
+    const NAME: &'static str = "authorization";
+
+    fn decode<'v, 'h: 'v, I>(values: I) -> Result<Self, HttpError>
+    where
+        I: Iterator<Item = &'v HeaderValue<'h>>,
+    {
+        let value: &str = single(Self::NAME, values)?;
+        let (scheme, credentials): (&str, &str) = value.split_once(' ').unwrap_or((value, ""));
+        Self::new(scheme, credentials.trim())
+    }
+
+    fn encode(&self) -> HeaderValue<'static> {
+        match self.credentials.is_empty() {
+            true => self.scheme.clone().into(),
+            false => format!("{} {}", self.scheme, self.credentials).into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheDirective {
+    NoCache,
+    NoStore,
+    NoTransform,
+    MustRevalidate,
+    Public,
+    Private,
+    Immutable,
+    MaxAge(u64),
+    SMaxAge(u64),
+    Extension(CacheExtension),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheExtension {
+    name: String,
+    value: Option<String>,
+}
+
+impl CacheExtension {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+}
+
+impl CacheDirective {
+    pub fn extension(name: &str, value: Option<&str>) -> Result<Self, HttpError> {
+        if !is_token(name) || !value.is_none_or(is_field_value) {
+            return Err(invalid(CacheControl::NAME));
+        }
+
+        Ok(CacheDirective::Extension(CacheExtension {
+            name: name.to_ascii_lowercase(),
+            value: value.map(str::to_owned),
+        }))
+    }
+}
+
+impl fmt::Display for CacheDirective {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CacheDirective::NoCache => write!(f, "no-cache"),
+            CacheDirective::NoStore => write!(f, "no-store"),
+            CacheDirective::NoTransform => write!(f, "no-transform"),
+            CacheDirective::MustRevalidate => write!(f, "must-revalidate"),
+            CacheDirective::Public => write!(f, "public"),
+            CacheDirective::Private => write!(f, "private"),
+            CacheDirective::Immutable => write!(f, "immutable"),
+            CacheDirective::MaxAge(seconds) => write!(f, "max-age={seconds}"),
+            CacheDirective::SMaxAge(seconds) => write!(f, "s-maxage={seconds}"),
+            CacheDirective::Extension(CacheExtension { name, value: None }) => write!(f, "{name}"),
+            CacheDirective::Extension(CacheExtension {
+                name,
+                value: Some(value),
+            }) => write!(f, "{name}={}", fmt_param_value(value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheControl(Vec<CacheDirective>);
+
+impl CacheControl {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn with(mut self, directive: CacheDirective) -> Self {
+        self.0.push(directive);
+        self
+    }
+
+    pub fn directives(&self) -> &[CacheDirective] {
+        &self.0
+    }
+
+    pub fn contains(&self, directive: &CacheDirective) -> bool {
+        self.0.contains(directive)
+    }
+
+    pub fn max_age(&self) -> Option<u64> {
+        self.0.iter().find_map(|directive: &CacheDirective| match directive {
+            CacheDirective::MaxAge(seconds) => Some(*seconds),
+            _ => None,
+        })
+    }
+}
+
+impl TypedHeader for CacheControl {
+    const NAME: &'static str = "cache-control";
+
+    fn decode<'v, 'h: 'v, I>(values: I) -> Result<Self, HttpError>
+    where
+        I: Iterator<Item = &'v HeaderValue<'h>>,
+    {
+        let directives: Vec<CacheDirective> = list(Self::NAME, values)?
+            .into_iter()
+            .map(|item: &str| {
+                let (name, value): (&str, Option<&str>) = match item.split_once('=') {
+                    Some((name, value)) => (name.trim(), Some(value.trim())),
+                    None => (item, None),
+                };
+
+                if !is_token(name) {
+                    return Err(invalid(Self::NAME));
+                }
+
+                let seconds = |value: Option<&str>| -> Result<u64, HttpError> {
+                    value
+                        .and_then(|value: &str| value.parse::<u64>().ok())
+                        .ok_or_else(|| invalid(Self::NAME))
+                };
+
+                Ok(match name.to_ascii_lowercase().as_str() {
+                    "no-cache" if value.is_none() => CacheDirective::NoCache,
+                    "no-store" => CacheDirective::NoStore,
+                    "no-transform" => CacheDirective::NoTransform,
+                    "must-revalidate" => CacheDirective::MustRevalidate,
+                    "public" => CacheDirective::Public,
+                    "private" if value.is_none() => CacheDirective::Private,
+                    "immutable" => CacheDirective::Immutable,
+                    "max-age" => CacheDirective::MaxAge(seconds(value)?),
+                    "s-maxage" => CacheDirective::SMaxAge(seconds(value)?),
+                    lowercase => {
+                        let value: Option<String> = value
+                            .map(|value: &str| parse_param_value(value).ok_or_else(|| invalid(Self::NAME)))
+                            .transpose()?;
+
+                        CacheDirective::extension(lowercase, value.as_deref())?
+                    }
+                })
+            })
+            .collect::<Result<Vec<CacheDirective>, HttpError>>()?;
+
+        Ok(Self(directives))
+    }
+
+    fn encode(&self) -> HeaderValue<'static> {
+        let directives: Vec<String> = self.0.iter().map(CacheDirective::to_string).collect();
+        directives.join(", ").into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ETag {
+    tag: String,
+    weak: bool,
+}
+
+impl ETag {
+    pub fn strong(tag: &str) -> Result<Self, HttpError> {
+        Self::new(tag, false)
+    }
+
+    pub fn weak(tag: &str) -> Result<Self, HttpError> {
+        Self::new(tag, true)
+    }
+
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    pub fn strong_eq(&self, other: &ETag) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    pub fn weak_eq(&self, other: &ETag) -> bool {
+        self.tag == other.tag
+    }
+
+    fn new(tag: &str, weak: bool) -> Result<Self, HttpError> {
+        let is_valid: bool = tag
+            .bytes()
+            .all(|byte: u8| byte == 0x21 || (0x23..=0x7e).contains(&byte) || byte >= 0x80);
+
+        if !is_valid {
+            return Err(invalid(Self::NAME));
+        }
+
+        Ok(Self { tag: tag.into(), weak })
+    }
+}
+
+impl TypedHeader for ETag {
+    const NAME: &'static str = "etag";
+
+    fn decode<'v, 'h: 'v, I>(values: I) -> Result<Self, HttpError>
+    where
+        I: Iterator<Item = &'v HeaderValue<'h>>,
+    {
+        let value: &str = single(Self::NAME, values)?;
+        let (weak, quoted): (bool, &str) = match value.strip_prefix("W/") {
+            Some(quoted) => (true, quoted),
+            None => (false, value),
+        };
+
+        let tag: &str = quoted
+            .strip_prefix(QUOTE)
+            .and_then(|tag: &str| tag.strip_suffix(QUOTE))
+            .ok_or_else(|| invalid(Self::NAME))?;
+
+        Self::new(tag, weak)
+    }
+
+    fn encode(&self) -> HeaderValue<'static> {
+        match self.weak {
+            true => format!("W/\"{}\"", self.tag).into(),
+            false => format!("\"{}\"", self.tag).into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Host {
+    hostname: String,
+    port: Option<u16>,
+}
+
+impl Host {
+    pub fn new(hostname: &str, port: Option<u16>) -> Result<Self, HttpError> {
+        let is_valid_hostname: bool = hostname
+            .bytes()
+            .all(|byte: u8| byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:[]%".contains(&byte));
+
+        if !is_valid_hostname {
+            return Err(invalid(Self::NAME));
+        }
+
+        Ok(Self {
+            hostname: hostname.into(),
+            port,
+        })
+    }
+
+    pub fn hostname(&self) -> &str {
+        &self.hostname
+    }
+
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+}
+
+impl TypedHeader for Host {
+    const NAME: &'static str = "host";
+
+    fn decode<'v, 'h: 'v, I>(values: I) -> Result<Self, HttpError>
+    where
+        I: Iterator<Item = &'v HeaderValue<'h>>,
+    {
+        let value: &str = single(Self::NAME, values)?;
+
+        let (hostname, port): (&str, Option<&str>) = if value.starts_with('[') {
+            let end: usize = value.find(']').ok_or_else(|| invalid(Self::NAME))?;
+            let port: Option<&str> = match &value[end + 1..] {
+                "" => None,
+                rest => Some(rest.strip_prefix(':').ok_or_else(|| invalid(Self::NAME))?),
+            };
+
+            (&value[..=end], port)
+        } else {
+            match value.split_once(':') {
+                Some((hostname, port)) => (hostname, Some(port)),
+                None => (value, None),
+            }
+        };
+
+        let port: Option<u16> = port
+            .map(|port: &str| port.parse::<u16>().map_err(|_| invalid(Self::NAME)))
+            .transpose()?;
+
+        Self::new(hostname, port)
+    }
+
+    fn encode(&self) -> HeaderValue<'static> {
+        match self.port {
+            Some(port) => format!("{}:{port}", self.hostname).into(),
+            None => self.hostname.clone().into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location(String);
+
+impl Location {
+    pub fn new(uri: &str) -> Result<Self, HttpError> {
+        if uri.is_empty() || !uri.bytes().all(|byte: u8| byte.is_ascii_graphic()) {
+            return Err(invalid(Self::NAME));
+        }
+
+        Ok(Self(uri.into()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TypedHeader for Location {
+    const NAME: &'static str = "location";
+
+    fn decode<'v, 'h: 'v, I>(values: I) -> Result<Self, HttpError>
+    where
+        I: Iterator<Item = &'v HeaderValue<'h>>,
+    {
+        Self::new(single(Self::NAME, values)?)
+    }
+
+    fn encode(&self) -> HeaderValue<'static> {
+        self.0.clone().into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserAgent(String);
+
+impl UserAgent {
+    pub fn new(user_agent: &str) -> Result<Self, HttpError> {
+        if user_agent.is_empty() || !is_field_value(user_agent) {
+            return Err(invalid(Self::NAME));
+        }
+
+        Ok(Self(user_agent.into()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TypedHeader for UserAgent {
+    const NAME: &'static str = "user-agent";
+
+    fn decode<'v, 'h: 'v, I>(values: I) -> Result<Self, HttpError>
+    where
+        I: Iterator<Item = &'v HeaderValue<'h>>,
+    {
+        Self::new(single(Self::NAME, values)?)
+    }
+
+    fn encode(&self) -> HeaderValue<'static> {
+        self.0.clone().into()
+    }
+}
+
+fn invalid(name: &str) -> HttpError {
+    warn!("Invalid {name} header");
+    HttpError::new(HttpStatus::BadRequest, format!("Invalid {name} header"))
+}
+
+fn is_field_value(value: &str) -> bool {
+    value
+        .bytes()
+        .all(|byte: u8| byte == b' ' || byte == b'\t' || byte.is_ascii_graphic() || byte >= 0x80)
+}
+
+fn single<'v, 'h: 'v, I>(name: &'static str, mut values: I) -> Result<&'v str, HttpError>
+where
+    I: Iterator<Item = &'v HeaderValue<'h>>,
+{
+    let value: &HeaderValue = values.next().ok_or_else(|| invalid(name))?;
+
+    if values.next().is_some() {
+        warn!("Multiple {name} headers received");
+        return Err(HttpError::new(HttpStatus::BadRequest, format!("Multiple {name} headers")));
+    }
+
+    value.to_str().map(str::trim).map_err(|_| invalid(name))
+}
+
+fn list<'v, 'h: 'v, I>(name: &'static str, values: I) -> Result<Vec<&'v str>, HttpError>
+where
+    I: Iterator<Item = &'v HeaderValue<'h>>,
+{
+    let mut items: Vec<&str> = Vec::new();
+
+    for value in values {
+        let value: &str = value.to_str().map_err(|_| invalid(name))?;
+        items.extend(split_unquoted(value, LIST_SEPARATOR));
+    }
+
+    Ok(items)
+}
+
+fn split_unquoted(value: &str, separator: char) -> impl Iterator<Item = &str> {
+    let mut quoted: bool = false;
+    let mut escaped: bool = false;
+
+    value
+        .split(move |c: char| {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if quoted => escaped = true,
+                QUOTE => quoted = !quoted,
+                _ => return c == separator && !quoted,
+            }
+
+            false
+        })
+        .map(str::trim)
+        .filter(|item: &&str| !item.is_empty())
+}
+
+fn parse_media_type(value: &str) -> Option<(String, Params)> {
+    let mut parts = split_unquoted(value, PARAMS_SEPARATOR);
+    let essence: &str = parts.next()?;
+    let (kind, subtype): (&str, &str) = essence.split_once('/')?;
+
+    if !is_token(kind) || !is_token(subtype) {
+        return None;
+    }
+
+    let params: Params = parts
+        .map(|param: &str| {
+            let (name, value): (&str, &str) = param.split_once('=')?;
+            let name: &str = name.trim();
+
+            if !is_token(name) {
+                return None;
+            }
+
+            Some((name.to_ascii_lowercase(), parse_param_value(value.trim())?))
+        })
+        .collect::<Option<Params>>()?;
+
+    Some((essence.to_ascii_lowercase(), params))
+}
+
+fn parse_param_value(value: &str) -> Option<String> {
+    let Some(quoted) = value.strip_prefix(QUOTE) else {
+        return is_token(value).then(|| value.into());
+    };
+
+    let mut chars = quoted.strip_suffix(QUOTE)?.chars();
+    let mut unquoted: String = String::with_capacity(quoted.len());
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unquoted.push(chars.next()?),
+            QUOTE => return None,
+            _ => unquoted.push(c),
+        }
+    }
+
+    Some(unquoted)
+}
+
+fn parse_quality(value: &str) -> Option<u16> {
+    let (integer, fraction): (&str, &str) = value.split_once('.').unwrap_or((value, ""));
+
+    if fraction.len() > 3 || !fraction.bytes().all(|byte: u8| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    let thousandths: u16 = format!("{fraction:0<3}").parse::<u16>().ok()?;
+
+    match integer {
+        "0" => Some(thousandths),
+        "1" if thousandths == 0 => Some(MAX_QUALITY),
+        _ => None,
+    }
+}
+
+fn find_param<'p>(params: &'p Params, name: &str) -> Option<&'p str> {
+    params
+        .iter()
+        .find(|(key, _): &&(String, String)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value): &(String, String)| value.as_str())
+}
+
+fn fmt_param_value(value: &str) -> String {
+    if is_token(value) {
+        return value.into();
+    }
+
+    let escaped: String = value.replace('\\', "\\\\").replace(QUOTE, "\\\"");
+    format!("\"{escaped}\"")
+}
+
+fn fmt_media_type(media_type: &str, params: &Params) -> String {
+    let mut encoded: String = media_type.into();
+
+    for (name, value) in params {
+        let _ = write!(encoded, "; {name}={}", fmt_param_value(value));
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode<T: TypedHeader>(values: &[&str]) -> Result<T, HttpError> {
+        let values: Vec<HeaderValue> = values.iter().map(|value: &&str| HeaderValue::from(*value)).collect();
+        T::decode(values.iter())
+    }
+
+    #[test]
+    fn test_content_type_roundtrip() {
+        let content_type: ContentType = decode(&["Application/JSON; Charset=\"utf-8\""]).unwrap();
+
+        assert_eq!(content_type.media_type(), "application/json");
+        assert_eq!(content_type.charset(), Some("utf-8"));
+        assert_eq!(content_type.encode(), "application/json; charset=utf-8");
+        assert_eq!(ContentType::text().encode(), "text/plain; charset=utf-8");
+    }
+
+    #[test]
+    fn test_content_type_invalid() {
+        for value in ["json", "text/", "text/plain; charset", "text/plain; charset=\"utf-8"] {
+            let result: Result<ContentType, HttpError> = decode(&[value]);
+            assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest, "Accepted: {value}");
+        }
+    }
+
+    #[test]
+    fn test_content_length() {
+        assert_eq!(decode::<ContentLength>(&["42"]).unwrap(), ContentLength(42));
+        assert_eq!(ContentLength(7).encode(), "7");
+
+        for values in [&["+1"][..], &["1 2"], &[""], &["1", "2"]] {
+            let result: Result<ContentLength, HttpError> = decode(values);
+            assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest, "Accepted: {values:?}");
+        }
+    }
+
+    #[test]
+    fn test_accept_quality_ordering() {
+        let accept: Accept = decode(&["text/html;q=0.5, application/json", "text/*;q=0.8, image/png;q=0"]).unwrap();
+        let preferred: Vec<&str> = accept.preferred().iter().map(|range| range.media_type()).collect();
+
+        assert_eq!(preferred, vec!["application/json", "text/*", "text/html"]);
+        assert!(accept.accepts("text/plain"));
+        assert!(!accept.accepts("image/png"));
+        assert_eq!(accept.ranges()[0].quality(), 0.5);
+    }
+
+    #[test]
+    fn test_accept_encode() {
+        let accept: Accept = Accept::new(vec![
+            MediaRange::new("application/json").unwrap(),
+            MediaRange::new("*/*").unwrap().with_quality(0.1),
+        ]);
+
+        assert_eq!(accept.encode(), "application/json, */*;q=0.1");
+    }
+
+    #[test]
+    fn test_accept_invalid_quality() {
+        for value in ["text/html;q=1.5", "text/html;q=0.1234", "text/html;q=abc"] {
+            let result: Result<Accept, HttpError> = decode(&[value]);
+            assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest, "Accepted: {value}");
+        }
+    }
+
+    #[test]
+    fn test_authorization() {
+        let authorization: Authorization = decode(&["Bearer abc.def.ghi"]).unwrap();
+
+        assert_eq!(authorization.scheme(), "Bearer");
+        assert_eq!(authorization.bearer_token(), Some("abc.def.ghi"));
+        assert_eq!(Authorization::bearer("token").unwrap().encode(), "Bearer token");
+
+        let basic: Authorization = decode(&["Basic dXNlcjpwYXNz"]).unwrap();
+        assert_eq!(basic.bearer_token(), None);
+        assert_eq!(basic.credentials(), "dXNlcjpwYXNz");
+
+        let result: Result<Authorization, HttpError> = decode(&["Bear(er) token"]);
+        assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest);
+    }
+
+    #[test]
+    fn test_authorization_errors_omit_credentials() {
+        let error: HttpError = decode::<Authorization>(&["Basic secret\u{1}token"]).unwrap_err();
+
+        assert_eq!(error.status, HttpStatus::BadRequest);
+        assert!(!error.message.contains("secret"));
+    }
+
+    #[test]
+    fn test_constructors_reject_header_injection() {
+        assert!(Authorization::new("Basic", "abc\r\nSet-Cookie: a=1").is_err());
+        assert!(Authorization::new("Bea rer", "abc").is_err());
+        assert!(Authorization::bearer("abc\ndef").is_err());
+        assert!(Host::new("example.com\r\nX-Injected: 1", None).is_err());
+        assert!(UserAgent::new("agent\r\nX-Injected: 1").is_err());
+        assert!(UserAgent::new("").is_err());
+        assert!(
+            ContentType::json()
+                .with_param("charset", "utf-8\r\nX-Injected: 1")
+                .is_err()
+        );
+        assert!(ContentType::json().with_param("char set", "utf-8").is_err());
+
+        let content_type: ContentType = ContentType::json().with_param("Charset", "utf-8").unwrap();
+        assert_eq!(content_type.encode(), "application/json; charset=utf-8");
+        assert_eq!(Host::new("example.com", Some(80)).unwrap().encode(), "example.com:80");
+
+        assert!(CacheDirective::extension("x\r\nSet-Cookie: a=b", None).is_err());
+        assert!(CacheDirective::extension("x-custom", Some("a\r\nSet-Cookie: a=b")).is_err());
+        assert!(CacheDirective::extension("x custom", None).is_err());
+
+        let encoded: HeaderValue = CacheControl::new()
+            .with(CacheDirective::extension("x-custom", Some("a b")).unwrap())
+            .encode();
+        assert_eq!(encoded, "x-custom=\"a b\"");
+    }
+
+    #[test]
+    fn test_cache_control() {
+        let cache_control: CacheControl = decode(&["public, max-age=3600", "no-transform, x-custom=\"a, b\""]).unwrap();
+
+        assert_eq!(cache_control.max_age(), Some(3600));
+        assert!(cache_control.contains(&CacheDirective::Public));
+        assert!(cache_control.contains(&CacheDirective::extension("X-Custom", Some("a, b")).unwrap()));
+
+        let encoded: HeaderValue = CacheControl::new()
+            .with(CacheDirective::NoStore)
+            .with(CacheDirective::MaxAge(0))
+            .encode();
+
+        assert_eq!(encoded, "no-store, max-age=0");
+
+        let result: Result<CacheControl, HttpError> = decode(&["max-age=soon"]);
+        assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest);
+    }
+
+    #[test]
+    fn test_etag() {
+        let strong: ETag = decode(&["\"xyzzy\""]).unwrap();
+        let weak: ETag = decode(&["W/\"xyzzy\""]).unwrap();
+
+        assert!(!strong.is_weak());
+        assert!(weak.is_weak());
+        assert!(strong.weak_eq(&weak));
+        assert!(!strong.strong_eq(&weak));
+        assert_eq!(weak.encode(), "W/\"xyzzy\"");
+
+        for value in ["xyzzy", "\"xy\"zzy\"", "W/xyzzy"] {
+            let result: Result<ETag, HttpError> = decode(&[value]);
+            assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest, "Accepted: {value}");
+        }
+    }
+
+    #[test]
+    fn test_host() {
+        let host: Host = decode(&["example.com:8080"]).unwrap();
+        assert_eq!(host.hostname(), "example.com");
+        assert_eq!(host.port(), Some(8080));
+
+        let ipv6: Host = decode(&["[::1]:3000"]).unwrap();
+        assert_eq!(ipv6.hostname(), "[::1]");
+        assert_eq!(ipv6.port(), Some(3000));
+        assert_eq!(ipv6.encode(), "[::1]:3000");
+
+        for values in [
+            &["example.com:http"][..],
+            &["exa mple.com"],
+            &["[::1"],
+            &["a.com", "b.com"],
+        ] {
+            let result: Result<Host, HttpError> = decode(values);
+            assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest, "Accepted: {values:?}");
+        }
+    }
+
+    #[test]
+    fn test_location_and_user_agent() {
+        assert_eq!(Location::new("/users/1").unwrap().encode(), "/users/1");
+        assert!(Location::new("/users/ 1").is_err());
+
+        let user_agent: UserAgent = decode(&["curl/8.5.0"]).unwrap();
+        assert_eq!(user_agent.as_str(), "curl/8.5.0");
+    }
+}
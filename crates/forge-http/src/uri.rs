@@ -0,0 +1,198 @@
+use std::borrow::Cow;
+use std::string::FromUtf8Error;
+
+use super::{HttpError, HttpStatus};
+use tracing::warn;
+
+const PERCENT: u8 = b'%';
+const PLUS: u8 = b'+';
+const PATH_SEPARATOR: char = '/';
+const CURRENT_SEGMENT: &str = ".";
+const PARENT_SEGMENT: &str = "..";
+
+pub fn normalize_path(raw: &str) -> Result<Cow<'_, str>, HttpError> {
+    let is_normalized: bool = !raw.contains(PERCENT as char)
+        && !raw
+            .split(PATH_SEPARATOR)
+            .any(|segment: &str| segment == CURRENT_SEGMENT || segment == PARENT_SEGMENT);
+
+    if is_normalized {
+        return Ok(Cow::Borrowed(raw));
+    }
+
+    let mut segments: Vec<Cow<str>> = Vec::new();
+
+    for segment in raw.split(PATH_SEPARATOR) {
+        let segment: Cow<str> = percent_decode(segment)?;
+
+        if segment.contains(PATH_SEPARATOR) {
+            warn!("Path '{raw}' contains an encoded path separator");
+            return Err(HttpError::new(
+                HttpStatus::BadRequest,
+                format!("Encoded path separator in path: \"{raw}\""),
+            ));
+        }
+
+        match segment.as_ref() {
+            "" | CURRENT_SEGMENT => {}
+            PARENT_SEGMENT => {
+                if segments.pop().is_none() {
+                    warn!("Path '{raw}' attempts to traverse above the root");
+                }
+            }
+            _ => segments.push(segment),
+        }
+    }
+
+    let mut normalized: String = String::with_capacity(raw.len() + 1);
+
+    for segment in segments {
+        normalized.push(PATH_SEPARATOR);
+        normalized.push_str(&segment);
+    }
+
+    if normalized.is_empty() {
+        normalized.push(PATH_SEPARATOR);
+    }
+
+    Ok(Cow::Owned(normalized))
+}
+
+pub fn percent_decode(input: &str) -> Result<Cow<'_, str>, HttpError> {
+    decode(input, false)
+}
+
+pub fn form_decode(input: &str) -> Result<Cow<'_, str>, HttpError> {
+    decode(input, true)
+}
+
+fn decode(input: &str, plus_as_space: bool) -> Result<Cow<'_, str>, HttpError> {
+    let bytes: &[u8] = input.as_bytes();
+
+    if !bytes
+        .iter()
+        .any(|byte: &u8| *byte == PERCENT || (plus_as_space && *byte == PLUS))
+    {
+        return Ok(Cow::Borrowed(input));
+    }
+
+    let mut decoded: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut position: usize = 0;
+
+    while let Some(&byte) = bytes.get(position) {
+        match byte {
+            PERCENT => {
+                let value: u8 = bytes
+                    .get(position + 1..position + 3)
+                    .and_then(decode_hex_pair)
+                    .ok_or_else(|| {
+                        warn!("Invalid percent-encoding in '{input}'");
+                        HttpError::new(HttpStatus::BadRequest, format!("Invalid percent-encoding: \"{input}\""))
+                    })?;
+
+                decoded.push(value);
+                position += 3;
+            }
+            PLUS if plus_as_space => {
+                decoded.push(b' ');
+                position += 1;
+            }
+            _ => {
+                decoded.push(byte);
+                position += 1;
+            }
+        }
+    }
+
+    String::from_utf8(decoded).map(Cow::Owned).map_err(|e: FromUtf8Error| {
+        warn!("Percent-decoded value is not valid UTF-8: {e}");
+        HttpError::new(
+            HttpStatus::BadRequest,
+            format!("Invalid UTF-8 in percent-encoded value: \"{input}\""),
+        )
+    })
+}
+
+fn decode_hex_pair(pair: &[u8]) -> Option<u8> {
+    let high: u32 = char::from(*pair.first()?).to_digit(16)?;
+    let low: u32 = char::from(*pair.get(1)?).to_digit(16)?;
+    u8::try_from(high * 16 + low).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_path_borrows_normalized_input() {
+        let normalized: Cow<str> = normalize_path("/files/a/b.txt").unwrap();
+        assert!(matches!(normalized, Cow::Borrowed("/files/a/b.txt")));
+    }
+
+    #[test]
+    fn test_normalize_path_decodes_segments() {
+        assert_eq!(normalize_path("/files/a%20b").unwrap(), "/files/a b");
+        assert_eq!(normalize_path("/files/caf%C3%A9/x").unwrap(), "/files/café/x");
+    }
+
+    #[test]
+    fn test_normalize_path_rejects_encoded_separator() {
+        for input in [
+            "/files/a%2Fb",
+            "/files/a%2fb",
+            "/static/..%2F..%2Fsecret",
+            "/static/%2e%2e%2Fsecret",
+        ] {
+            let result: Result<Cow<str>, HttpError> = normalize_path(input);
+            assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest, "Accepted: {input}");
+        }
+    }
+
+    #[test]
+    fn test_normalize_path_removes_dot_segments() {
+        assert_eq!(normalize_path("/files/./x/../y").unwrap(), "/files/y");
+        assert_eq!(normalize_path("/a/b/c/./../../g").unwrap(), "/a/g");
+        assert_eq!(normalize_path("/a/..").unwrap(), "/");
+        assert_eq!(normalize_path("/a/..b/c.").unwrap(), "/a/..b/c.");
+    }
+
+    #[test]
+    fn test_normalize_path_traversal_stays_at_root() {
+        assert_eq!(normalize_path("/../../etc/passwd").unwrap(), "/etc/passwd");
+        assert_eq!(normalize_path("/static/%2e%2e/%2E%2E/secret").unwrap(), "/secret");
+    }
+
+    #[test]
+    fn test_normalize_path_invalid_encoding() {
+        for input in ["/files/%", "/files/%2x", "/files/%FF"] {
+            let result: Result<Cow<str>, HttpError> = normalize_path(input);
+            assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest, "Accepted: {input}");
+        }
+    }
+
+    #[test]
+    fn test_percent_decode_borrows_plain_input() {
+        let decoded: Cow<str> = percent_decode("plain-value").unwrap();
+        assert!(matches!(decoded, Cow::Borrowed("plain-value")));
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("a%20b%2Fc").unwrap(), "a b/c");
+        assert_eq!(percent_decode("caf%C3%A9").unwrap(), "café");
+        assert_eq!(percent_decode("1+1").unwrap(), "1+1");
+    }
+
+    #[test]
+    fn test_form_decode_plus_as_space() {
+        assert_eq!(form_decode("hello+world%21").unwrap(), "hello world!");
+    }
+
+    #[test]
+    fn test_percent_decode_invalid_sequences() {
+        for input in ["%", "%2", "%zz", "abc%g0", "%C3%28"] {
+            let result: Result<Cow<str>, HttpError> = percent_decode(input);
+            assert_eq!(result.unwrap_err().status, HttpStatus::BadRequest, "Accepted: {input}");
+        }
+    }
+}
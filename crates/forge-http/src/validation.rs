@@ -0,0 +1,275 @@
+use super::headers::{is_token, is_token_char};
+use super::{HeaderMap, HeaderValue, HttpError, HttpStatus, HttpVersion};
+use tracing::warn;
+
+const CONTENT_LENGTH: &str = "content-length";
+const TRANSFER_ENCODING: &str = "transfer-encoding";
+const HOST: &str = "host";
+const CHUNKED: &str = "chunked";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParseOptions {
+    pub strict: bool,
+}
+
+impl ParseOptions {
+    pub fn strict() -> Self {
+        Self { strict: true }
+    }
+
+    pub fn lenient() -> Self {
+        Self { strict: false }
+    }
+}
+
+pub(crate) fn validate_line_endings(head: &[u8]) -> Result<(), HttpError> {
+    for (i, byte) in head.iter().enumerate() {
+        match byte {
+            b'\n' if i == 0 || head[i - 1] != b'\r' => return Err(reject("Bare LF line terminator in request head")),
+            b'\r' if head.get(i + 1) != Some(&b'\n') => return Err(reject("Bare CR in request head")),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn validate_request_line(line: &str) -> Result<(), HttpError> {
+    let parts: Vec<&str> = line.split(' ').collect();
+
+    let [method, target, _version] = parts.as_slice() else {
+        return Err(reject("Request line must be \"method SP request-target SP HTTP-version\""));
+    };
+
+    if !is_token(method) {
+        return Err(reject(format!("Invalid method token: \"{method}\"")));
+    }
+
+    if target.is_empty() || !target.bytes().all(|byte: u8| byte.is_ascii_graphic()) {
+        return Err(reject("Request target contains invalid characters"));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn validate_header_line(line: &[u8]) -> Result<(), HttpError> {
+    if matches!(line.first(), Some(b' ' | b'\t')) {
+        return Err(reject("Obsolete line folding is not allowed"));
+    }
+
+    let Some(separator) = line.iter().position(|byte: &u8| *byte == b':') else {
+        return Ok(());
+    };
+
+    let (name, value): (&[u8], &[u8]) = (&line[..separator], &line[separator + 1..]);
+
+    if matches!(name.last(), Some(b' ' | b'\t')) {
+        return Err(reject("Whitespace between header name and colon is not allowed"));
+    }
+
+    if name.is_empty() || !name.iter().all(|byte: &u8| is_token_char(*byte)) {
+        return Err(reject(format!("Invalid header name: \"{}\"", String::from_utf8_lossy(name))));
+    }
+
+    if value.iter().any(|byte: &u8| *byte != b'\t' && byte.is_ascii_control()) {
+        return Err(reject(format!(
+            "Invalid character in value of header \"{}\"",
+            String::from_utf8_lossy(name)
+        )));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn validate_framing(headers: &HeaderMap, version: HttpVersion) -> Result<(), HttpError> {
+    let mut content_lengths = headers.get_all(CONTENT_LENGTH);
+
+    if let Some(first) = content_lengths.next() {
+        if content_lengths.any(|value: &HeaderValue| value != first) {
+            return Err(reject("Conflicting Content-Length headers"));
+        }
+
+        if headers.get_all(CONTENT_LENGTH).count() > 1 {
+            return Err(reject("Duplicate Content-Length headers"));
+        }
+
+        if first.is_empty() || !first.as_bytes().iter().all(u8::is_ascii_digit) {
+            return Err(reject(format!("Invalid Content-Length value: \"{first}\"")));
+        }
+
+        if headers.contains_key(TRANSFER_ENCODING) {
+            return Err(reject("Request contains both Content-Length and Transfer-Encoding"));
+        }
+    }
+
+    if headers.contains_key(TRANSFER_ENCODING) {
+        let codings: Vec<&str> = transfer_codings(headers)?;
+        let is_chunked = |coding: &str| -> bool { coding.eq_ignore_ascii_case(CHUNKED) };
+        let chunked_count: usize = codings.iter().filter(|coding: &&&str| is_chunked(coding)).count();
+
+        if chunked_count != 1 || !codings.last().is_some_and(|coding: &&str| is_chunked(coding)) {
+            return Err(reject(format!(
+                "Transfer-Encoding must end with a single chunked coding: \"{}\"",
+                codings.join(", ")
+            )));
+        }
+    }
+
+    match headers.get_all(HOST).count() {
+        0 if version == HttpVersion::Http11 => Err(reject("Missing Host header")),
+        0 | 1 => Ok(()),
+        _ => Err(reject("Multiple Host headers")),
+    }
+}
+
+pub(crate) fn transfer_codings<'b>(headers: &'b HeaderMap) -> Result<Vec<&'b str>, HttpError> {
+    let mut codings: Vec<&str> = Vec::new();
+
+    for value in headers.get_all(TRANSFER_ENCODING) {
+        codings.extend(
+            value
+                .to_str()?
+                .split(',')
+                .map(str::trim)
+                .filter(|coding: &&str| !coding.is_empty()),
+        );
+    }
+
+    Ok(codings)
+}
+
+fn reject(message: impl Into<String>) -> HttpError {
+    let message: String = message.into();
+    warn!("Strict validation failed: {message}");
+    HttpError::new(HttpStatus::BadRequest, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Limits, Request};
+
+    fn parse_strict(raw: &[u8]) -> Result<Request<'_>, HttpError> {
+        Request::parse_with(raw, &ParseOptions::strict(), &Limits::default()).map(|(request, _)| request)
+    }
+
+    fn assert_rejected(raw: &[u8], message: &str) {
+        let error: HttpError = parse_strict(raw).expect_err("Strict mode should reject request");
+
+        assert_eq!(error.status, HttpStatus::BadRequest);
+        assert!(error.message.contains(message), "Unexpected message: {}", error.message);
+    }
+
+    #[test]
+    fn test_strict_accepts_valid_request() {
+        let raw: &[u8] = b"POST /submit HTTP/1.1\r\nHost: localhost\r\nContent-Length: 2\r\nX-Tab:\tvalue\r\n\r\nok";
+        let request: Request = parse_strict(raw).expect("Should accept a well-formed request");
+
+        assert_eq!(request.content_length().unwrap(), Some(2));
+    }
+
+    #[test]
+    fn test_strict_rejects_bare_lf() {
+        assert_rejected(b"GET / HTTP/1.1\nHost: localhost\n\n", "Bare LF");
+        assert_rejected(b"GET / HTTP/1.1\r\nHost: localhost\n\r\n", "Bare LF");
+    }
+
+    #[test]
+    fn test_strict_rejects_bare_cr() {
+        assert_rejected(b"GET / HTTP/1.1\r\nHost: local\rhost\r\n\r\n", "Bare CR");
+    }
+
+    #[test]
+    fn test_strict_rejects_obs_fold() {
+        assert_rejected(
+            b"GET / HTTP/1.1\r\nHost: localhost\r\nX-Long: a\r\n  b\r\n\r\n",
+            "Obsolete line folding",
+        );
+    }
+
+    #[test]
+    fn test_strict_rejects_whitespace_before_colon() {
+        assert_rejected(
+            b"GET / HTTP/1.1\r\nHost : localhost\r\n\r\n",
+            "Whitespace between header name and colon",
+        );
+    }
+
+    #[test]
+    fn test_strict_rejects_invalid_header_name() {
+        assert_rejected(
+            b"GET / HTTP/1.1\r\nHost: localhost\r\nBad[Name]: 1\r\n\r\n",
+            "Invalid header name",
+        );
+    }
+
+    #[test]
+    fn test_strict_rejects_control_characters_in_value() {
+        assert_rejected(b"GET / HTTP/1.1\r\nHost: local\x00host\r\n\r\n", "Invalid character in value");
+    }
+
+    #[test]
+    fn test_strict_rejects_duplicate_content_length() {
+        assert_rejected(
+            b"POST / HTTP/1.1\r\nHost: a\r\nContent-Length: 5\r\nContent-Length: 5\r\n\r\n",
+            "Duplicate Content-Length",
+        );
+        assert_rejected(
+            b"POST / HTTP/1.1\r\nHost: a\r\nContent-Length: 5\r\nContent-Length: 6\r\n\r\n",
+            "Conflicting Content-Length",
+        );
+        assert_rejected(
+            b"POST / HTTP/1.1\r\nHost: a\r\nContent-Length: 5, 5\r\n\r\n",
+            "Invalid Content-Length",
+        );
+    }
+
+    #[test]
+    fn test_strict_rejects_content_length_with_transfer_encoding() {
+        assert_rejected(
+            b"POST / HTTP/1.1\r\nHost: a\r\nContent-Length: 5\r\nTransfer-Encoding: chunked\r\n\r\n",
+            "both Content-Length and Transfer-Encoding",
+        );
+    }
+
+    #[test]
+    fn test_strict_rejects_ambiguous_transfer_encoding() {
+        assert_rejected(
+            b"POST / HTTP/1.1\r\nHost: a\r\nTransfer-Encoding: chunked\r\nTransfer-Encoding: gzip\r\n\r\n",
+            "single chunked coding",
+        );
+        assert_rejected(
+            b"POST / HTTP/1.1\r\nHost: a\r\nTransfer-Encoding: chunked\r\nTransfer-Encoding: chunked\r\n\r\n",
+            "single chunked coding",
+        );
+        assert_rejected(
+            b"POST / HTTP/1.1\r\nHost: a\r\nTransfer-Encoding: gzip\r\n\r\n",
+            "single chunked coding",
+        );
+
+        let raw: &[u8] = b"POST / HTTP/1.1\r\nHost: a\r\nTransfer-Encoding: gzip\r\nTransfer-Encoding: chunked\r\n\r\n";
+        assert!(parse_strict(raw).unwrap().is_chunked().unwrap());
+    }
+
+    #[test]
+    fn test_strict_host_requirements() {
+        assert_rejected(b"GET / HTTP/1.1\r\n\r\n", "Missing Host");
+        assert_rejected(b"GET / HTTP/1.1\r\nHost: a\r\nHost: b\r\n\r\n", "Multiple Host");
+        assert!(parse_strict(b"GET / HTTP/1.0\r\n\r\n").is_ok());
+    }
+
+    #[test]
+    fn test_strict_rejects_malformed_request_line() {
+        assert_rejected(b"GET  / HTTP/1.1\r\nHost: a\r\n\r\n", "Request line must be");
+        assert_rejected(b"GET / HTTP/1.1 extra\r\nHost: a\r\n\r\n", "Request line must be");
+    }
+
+    #[test]
+    fn test_lenient_mode_accepts_legacy_input() {
+        let raw: &[u8] = b"GET / HTTP/1.1\nHost : localhost\n\n";
+        let (request, _): (Request, usize) =
+            Request::parse_with(raw, &ParseOptions::lenient(), &Limits::default()).unwrap();
+
+        assert_eq!(request.header("host").unwrap(), Some("localhost"));
+    }
+}
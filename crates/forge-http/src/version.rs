@@ -0,0 +1,110 @@
+use core::fmt;
+use std::str;
+
+use super::HttpError;
+use super::HttpStatus;
+
+const VERSION_PREFIX: &str = "HTTP/";
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub enum HttpVersion {
+    Http10,
+    #[default]
+    Http11,
+    Http2,
+}
+
+impl HttpVersion {
+    pub fn keep_alive_by_default(&self) -> bool {
+        matches!(self, HttpVersion::Http11 | HttpVersion::Http2)
+    }
+
+    pub fn supports_chunked(&self) -> bool {
+        matches!(self, HttpVersion::Http11)
+    }
+}
+
+impl fmt::Display for HttpVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg: &'static str = match self {
+            HttpVersion::Http10 => "HTTP/1.0",
+            HttpVersion::Http11 => "HTTP/1.1",
+            HttpVersion::Http2 => "HTTP/2",
+        };
+
+        write!(f, "{msg}")
+    }
+}
+
+impl str::FromStr for HttpVersion {
+    type Err = HttpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "HTTP/1.0" => Ok(HttpVersion::Http10),
+            "HTTP/1.1" => Ok(HttpVersion::Http11),
+            _ if is_well_formed(s) => Err(HttpError::new(
+                HttpStatus::HttpVersionNotSupported,
+                format!("Unsupported HTTP version: \"{s}\""),
+            )),
+            _ => Err(HttpError::new(
+                HttpStatus::BadRequest,
+                format!("Malformed HTTP version: \"{s}\""),
+            )),
+        }
+    }
+}
+
+fn is_well_formed(version: &str) -> bool {
+    let Some(number) = version.strip_prefix(VERSION_PREFIX) else {
+        return false;
+    };
+
+    match number.split_once('.') {
+        Some((major, minor)) => [major, minor]
+            .iter()
+            .all(|part: &&str| part.len() == 1 && part.bytes().all(|byte: u8| byte.is_ascii_digit())),
+        None => number.len() == 1 && number.bytes().all(|byte: u8| byte.is_ascii_digit()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_supported_versions() {
+        assert_eq!(HttpVersion::from_str("HTTP/1.0").unwrap(), HttpVersion::Http10);
+        assert_eq!(HttpVersion::from_str("HTTP/1.1").unwrap(), HttpVersion::Http11);
+        assert_eq!(HttpVersion::Http10.to_string(), "HTTP/1.0");
+        assert_eq!(HttpVersion::Http11.to_string(), "HTTP/1.1");
+    }
+
+    #[test]
+    fn test_unsupported_versions_are_505() {
+        for version in ["HTTP/0.9", "HTTP/1.2", "HTTP/2.0", "HTTP/2", "HTTP/3"] {
+            let error: HttpError = HttpVersion::from_str(version).unwrap_err();
+            assert_eq!(error.status, HttpStatus::HttpVersionNotSupported, "{version}");
+        }
+    }
+
+    #[test]
+    fn test_malformed_versions_are_400() {
+        for version in ["http/1.1", "HTTP/1.10", "HTTP/", "HTTP/a.b", "FOO"] {
+            let error: HttpError = HttpVersion::from_str(version).unwrap_err();
+            assert_eq!(error.status, HttpStatus::BadRequest, "{version}");
+        }
+    }
+
+    #[test]
+    fn test_version_semantics() {
+        assert!(!HttpVersion::Http10.keep_alive_by_default());
+        assert!(!HttpVersion::Http10.supports_chunked());
+        assert!(HttpVersion::Http11.keep_alive_by_default());
+        assert!(HttpVersion::Http11.supports_chunked());
+        assert!(HttpVersion::Http2.keep_alive_by_default());
+        assert!(!HttpVersion::Http2.supports_chunked());
+        assert_eq!(HttpVersion::Http2.to_string(), "HTTP/2");
+    }
+}
@@ -0,0 +1,770 @@
+use std::io::{self, ErrorKind};
+use std::str::{self, Utf8Error};
+
+use super::upgrade::Upgraded;
+use super::{HeaderValue, HttpError, HttpMethod, HttpStatus, HttpVersion, Request, Response};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use sha1::{Digest, Sha1};
+use thiserror::Error;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing::{debug, trace, warn};
+
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+const SUPPORTED_VERSION: &str = "13";
+const KEY_LENGTH: usize = 16;
+const MAX_CONTROL_PAYLOAD: usize = 125;
+const READ_BUFFER_SIZE: usize = 4096;
+
+#[derive(Debug, Clone, Copy)]
+pub struct WebSocketConfig {
+    pub max_frame_size: usize,
+    pub max_message_size: usize,
+}
+
+impl Default for WebSocketConfig {
+    fn default() -> Self {
+        Self {
+            max_frame_size: 16 * 1024 * 1024,
+            max_message_size: 64 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloseFrame {
+    pub code: u16,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    Text(String),
+    Binary(Vec<u8>),
+    Ping(Vec<u8>),
+    Pong(Vec<u8>),
+    Close(Option<CloseFrame>),
+}
+
+#[derive(Debug, Error)]
+pub enum WebSocketError {
+    #[error("WebSocket protocol violation: {0}")]
+    Protocol(&'static str),
+
+    #[error("WebSocket frame of {0} bytes exceeds the limit")]
+    FrameTooLarge(usize),
+
+    #[error("WebSocket message of {0} bytes exceeds the limit")]
+    MessageTooLarge(usize),
+
+    #[error("WebSocket text message is not valid UTF-8")]
+    InvalidUtf8,
+
+    #[error("WebSocket connection is closed")]
+    Closed,
+
+    #[error("WebSocket I/O error: {0}")]
+    Io(#[from] io::Error),
+}
+
+impl WebSocketError {
+    fn close_code(&self) -> Option<u16> {
+        match self {
+            WebSocketError::Protocol(_) => Some(1002),
+            WebSocketError::InvalidUtf8 => Some(1007),
+            WebSocketError::FrameTooLarge(_) | WebSocketError::MessageTooLarge(_) => Some(1009),
+            WebSocketError::Closed | WebSocketError::Io(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Role {
+    Server,
+    Client,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OpCode {
+    Continuation,
+    Text,
+    Binary,
+    Close,
+    Ping,
+    Pong,
+}
+
+impl OpCode {
+    fn is_control(&self) -> bool {
+        matches!(self, OpCode::Close | OpCode::Ping | OpCode::Pong)
+    }
+}
+
+impl TryFrom<u8> for OpCode {
+    type Error = WebSocketError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x0 => Ok(OpCode::Continuation),
+            0x1 => Ok(OpCode::Text),
+            0x2 => Ok(OpCode::Binary),
+            0x8 => Ok(OpCode::Close),
+            0x9 => Ok(OpCode::Ping),
+            0xA => Ok(OpCode::Pong),
+            _ => Err(WebSocketError::Protocol("Reserved opcode")),
+        }
+    }
+}
+
+impl From<OpCode> for u8 {
+    fn from(opcode: OpCode) -> u8 {
+        match opcode {
+            OpCode::Continuation => 0x0,
+            OpCode::Text => 0x1,
+            OpCode::Binary => 0x2,
+            OpCode::Close => 0x8,
+            OpCode::Ping => 0x9,
+            OpCode::Pong => 0xA,
+        }
+    }
+}
+
+struct Frame {
+    fin: bool,
+    opcode: OpCode,
+    payload: Vec<u8>,
+}
+
+pub struct WebSocketUpgrade {
+    accept: String,
+    config: WebSocketConfig,
+}
+
+impl WebSocketUpgrade {
+    pub fn from_request(request: &Request) -> Result<Self, HttpError> {
+        if request.method != HttpMethod::GET || request.version != HttpVersion::Http11 {
+            warn!("WebSocket upgrade attempted with {} {}", request.method, request.version);
+            return Err(HttpError::new(
+                HttpStatus::BadRequest,
+                "WebSocket upgrade requires an HTTP/1.1 GET request",
+            ));
+        }
+
+        if !Self::has_token(request, "connection", "upgrade") || !Self::has_token(request, "upgrade", "websocket") {
+            warn!("Request is missing the WebSocket upgrade headers");
+            return Err(HttpError::new(HttpStatus::UpgradeRequired, "Expected a WebSocket upgrade"));
+        }
+
+        if request.header("sec-websocket-version")? != Some(SUPPORTED_VERSION) {
+            warn!("Unsupported WebSocket version requested");
+            return Err(HttpError::new(HttpStatus::UpgradeRequired, "Unsupported WebSocket version"));
+        }
+
+        let key: &str = request
+            .header("sec-websocket-key")?
+            .map(str::trim)
+            .filter(|key: &&str| {
+                STANDARD
+                    .decode(key)
+                    .is_ok_and(|decoded: Vec<u8>| decoded.len() == KEY_LENGTH)
+            })
+            .ok_or_else(|| {
+                warn!("Missing or invalid Sec-WebSocket-Key header");
+                HttpError::new(HttpStatus::BadRequest, "Missing or invalid Sec-WebSocket-Key header")
+            })?;
+
+        Ok(Self {
+            accept: accept_key(key),
+            config: WebSocketConfig::default(),
+        })
+    }
+
+    pub fn with_config(mut self, config: WebSocketConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn on_upgrade<F, Fut>(self, handler: F) -> Response<'static>
+    where
+        F: FnOnce(WebSocket) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let config: WebSocketConfig = self.config;
+
+        Response::new(HttpStatus::SwitchingProtocols)
+            .header("Upgrade", "websocket")
+            .header("Connection", "Upgrade")
+            .header("Sec-WebSocket-Accept", self.accept)
+            .on_upgrade(move |io: Upgraded| async move {
+                debug!("Connection upgraded to WebSocket");
+                handler(WebSocket::server(io, config)).await;
+            })
+    }
+
+    fn has_token(request: &Request, name: &str, token: &str) -> bool {
+        request
+            .headers
+            .get_all(name)
+            .filter_map(|value: &HeaderValue| value.to_str().ok())
+            .flat_map(|value: &str| value.split(','))
+            .any(|option: &str| option.trim().eq_ignore_ascii_case(token))
+    }
+}
+
+pub fn accept_key(key: &str) -> String {
+    let mut hasher: Sha1 = Sha1::new();
+    hasher.update(key.as_bytes());
+    hasher.update(ACCEPT_GUID.as_bytes());
+    STANDARD.encode(hasher.finalize())
+}
+
+pub struct WebSocket {
+    io: Upgraded,
+    role: Role,
+    config: WebSocketConfig,
+    buffer: Vec<u8>,
+    fragments: Option<(OpCode, Vec<u8>)>,
+    close_sent: bool,
+    close_received: bool,
+}
+
+impl WebSocket {
+    pub fn server(io: Upgraded, config: WebSocketConfig) -> Self {
+        Self::new(io, Role::Server, config)
+    }
+
+    pub fn client(io: Upgraded, config: WebSocketConfig) -> Self {
+        Self::new(io, Role::Client, config)
+    }
+
+    pub fn new(io: Upgraded, role: Role, config: WebSocketConfig) -> Self {
+        Self {
+            io,
+            role,
+            config,
+            buffer: Vec::with_capacity(READ_BUFFER_SIZE),
+            fragments: None,
+            close_sent: false,
+            close_received: false,
+        }
+    }
+
+    pub async fn recv(&mut self) -> Option<Result<Message, WebSocketError>> {
+        if self.close_received {
+            return None;
+        }
+
+        match self.read_message().await {
+            Ok(message) => Some(Ok(message)),
+            Err(e) => {
+                self.close_received = true;
+
+                if let Some(code) = e.close_code() {
+                    warn!("Closing WebSocket with {code}: {e}");
+                    let frame: CloseFrame = CloseFrame {
+                        code,
+                        reason: String::new(),
+                    };
+
+                    if let Err(e) = self.send(Message::Close(Some(frame))).await {
+                        debug!("Failed to send WebSocket close frame: {e}");
+                    }
+                }
+
+                Some(Err(e))
+            }
+        }
+    }
+
+    pub async fn send(&mut self, message: Message) -> Result<(), WebSocketError> {
+        if self.close_sent {
+            return Err(WebSocketError::Closed);
+        }
+
+        match message {
+            Message::Text(text) => self.write_frame(OpCode::Text, text.as_bytes()).await,
+            Message::Binary(data) => self.write_frame(OpCode::Binary, &data).await,
+            Message::Ping(data) => self.write_control_frame(OpCode::Ping, &data).await,
+            Message::Pong(data) => self.write_control_frame(OpCode::Pong, &data).await,
+            Message::Close(frame) => {
+                let mut payload: Vec<u8> = Vec::new();
+
+                if let Some(frame) = frame {
+                    if !is_valid_close_code(frame.code) {
+                        return Err(WebSocketError::Protocol("Invalid close code"));
+                    }
+
+                    payload.extend_from_slice(&frame.code.to_be_bytes());
+                    payload.extend_from_slice(frame.reason.as_bytes());
+                }
+
+                if payload.len() > MAX_CONTROL_PAYLOAD {
+                    return Err(WebSocketError::Protocol("Control frame payload is too long"));
+                }
+
+                self.close_sent = true;
+                self.write_frame(OpCode::Close, &payload).await
+            }
+        }
+    }
+
+    pub async fn close(&mut self, code: u16, reason: impl Into<String>) -> Result<(), WebSocketError> {
+        let frame: CloseFrame = CloseFrame {
+            code,
+            reason: reason.into(),
+        };
+
+        self.send(Message::Close(Some(frame))).await
+    }
+
+    async fn write_control_frame(&mut self, opcode: OpCode, payload: &[u8]) -> Result<(), WebSocketError> {
+        if payload.len() > MAX_CONTROL_PAYLOAD {
+            return Err(WebSocketError::Protocol("Control frame payload is too long"));
+        }
+
+        self.write_frame(opcode, payload).await
+    }
+
+    async fn read_message(&mut self) -> Result<Message, WebSocketError> {
+        loop {
+            let frame: Frame = self.read_frame().await?;
+
+            match frame.opcode {
+                OpCode::Ping => {
+                    if !self.close_sent {
+                        self.write_frame(OpCode::Pong, &frame.payload).await?;
+                    }
+
+                    return Ok(Message::Ping(frame.payload));
+                }
+                OpCode::Pong => return Ok(Message::Pong(frame.payload)),
+                OpCode::Close => {
+                    let close: Option<CloseFrame> = Self::parse_close(&frame.payload)?;
+                    self.close_received = true;
+
+                    if !self.close_sent {
+                        self.send(Message::Close(close.clone())).await?;
+                    }
+
+                    return Ok(Message::Close(close));
+                }
+                OpCode::Text | OpCode::Binary => {
+                    if self.fragments.is_some() {
+                        return Err(WebSocketError::Protocol("Expected a continuation frame"));
+                    }
+
+                    if frame.payload.len() > self.config.max_message_size {
+                        return Err(WebSocketError::MessageTooLarge(frame.payload.len()));
+                    }
+
+                    if frame.fin {
+                        return Self::into_message(frame.opcode, frame.payload);
+                    }
+
+                    self.fragments = Some((frame.opcode, frame.payload));
+                }
+                OpCode::Continuation => {
+                    let Some((opcode, mut payload)) = self.fragments.take() else {
+                        return Err(WebSocketError::Protocol("Unexpected continuation frame"));
+                    };
+
+                    let length: usize = payload.len() + frame.payload.len();
+
+                    if length > self.config.max_message_size {
+                        return Err(WebSocketError::MessageTooLarge(length));
+                    }
+
+                    payload.extend_from_slice(&frame.payload);
+
+                    if frame.fin {
+                        return Self::into_message(opcode, payload);
+                    }
+
+                    self.fragments = Some((opcode, payload));
+                }
+            }
+        }
+    }
+
+    async fn read_frame(&mut self) -> Result<Frame, WebSocketError> {
+        self.fill(2).await?;
+
+        let (first, second): (u8, u8) = (self.buffer[0], self.buffer[1]);
+        let fin: bool = first & 0x80 != 0;
+        let opcode: OpCode = OpCode::try_from(first & 0x0F)?;
+        let masked: bool = second & 0x80 != 0;
+
+        if first & 0x70 != 0 {
+            return Err(WebSocketError::Protocol("Reserved bits must be zero"));
+        }
+
+        if masked != (self.role == Role::Server) {
+            return Err(WebSocketError::Protocol("Invalid frame masking"));
+        }
+
+        let (length, mut offset): (u64, usize) = match second & 0x7F {
+            126 => {
+                self.fill(4).await?;
+                (u64::from(u16::from_be_bytes([self.buffer[2], self.buffer[3]])), 4)
+            }
+            127 => {
+                self.fill(10).await?;
+                let bytes: [u8; 8] = self.buffer[2..10].try_into().unwrap_or_default();
+                (u64::from_be_bytes(bytes), 10)
+            }
+            length => (u64::from(length), 2),
+        };
+
+        let length: usize = usize::try_from(length).map_err(|_| WebSocketError::FrameTooLarge(usize::MAX))?;
+
+        if opcode.is_control() && (!fin || length > MAX_CONTROL_PAYLOAD) {
+            return Err(WebSocketError::Protocol("Invalid control frame"));
+        }
+
+        if length > self.config.max_frame_size {
+            return Err(WebSocketError::FrameTooLarge(length));
+        }
+
+        let mask: Option<[u8; 4]> = match masked {
+            true => {
+                self.fill(offset + 4).await?;
+                let mask: [u8; 4] = self.buffer[offset..offset + 4].try_into().unwrap_or_default();
+                offset += 4;
+                Some(mask)
+            }
+            false => None,
+        };
+
+        self.fill(offset + length).await?;
+        let mut payload: Vec<u8> = self.buffer[offset..offset + length].to_vec();
+        self.buffer.drain(..offset + length);
+
+        if let Some(mask) = mask {
+            apply_mask(&mut payload, mask);
+        }
+
+        trace!("Read WebSocket {opcode:?} frame of {length} bytes (fin: {fin})");
+        Ok(Frame { fin, opcode, payload })
+    }
+
+    async fn write_frame(&mut self, opcode: OpCode, payload: &[u8]) -> Result<(), WebSocketError> {
+        let mut frame: Vec<u8> = Vec::with_capacity(payload.len() + 14);
+        frame.push(0x80 | u8::from(opcode));
+
+        let mask_bit: u8 = match self.role {
+            Role::Server => 0x00,
+            Role::Client => 0x80,
+        };
+
+        match payload.len() {
+            length @ 0..=125 => frame.push(mask_bit | length as u8),
+            length @ 126..=0xFFFF => {
+                frame.push(mask_bit | 126);
+                frame.extend_from_slice(&(length as u16).to_be_bytes());
+            }
+            length => {
+                frame.push(mask_bit | 127);
+                frame.extend_from_slice(&(length as u64).to_be_bytes());
+            }
+        }
+
+        let start: usize = frame.len();
+
+        match self.role {
+            Role::Server => frame.extend_from_slice(payload),
+            Role::Client => {
+                let mask: [u8; 4] = masking_key()?;
+                frame.extend_from_slice(&mask);
+                frame.extend_from_slice(payload);
+                apply_mask(&mut frame[start + 4..], mask);
+            }
+        }
+
+        self.io.write_all(&frame).await?;
+        self.io.flush().await?;
+
+        trace!("Wrote WebSocket {opcode:?} frame of {} bytes", payload.len());
+        Ok(())
+    }
+
+    async fn fill(&mut self, length: usize) -> Result<(), WebSocketError> {
+        while self.buffer.len() < length {
+            self.buffer.reserve(READ_BUFFER_SIZE.max(length - self.buffer.len()));
+
+            if self.io.read_buf(&mut self.buffer).await? == 0 {
+                return Err(io::Error::from(ErrorKind::UnexpectedEof).into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_close(payload: &[u8]) -> Result<Option<CloseFrame>, WebSocketError> {
+        let (code, reason): (&[u8], &[u8]) = match payload.len() {
+            0 => return Ok(None),
+            1 => return Err(WebSocketError::Protocol("Close frame payload is too short")),
+            _ => payload.split_at(2),
+        };
+
+        let code: u16 = u16::from_be_bytes([code[0], code[1]]);
+
+        if !is_valid_close_code(code) {
+            return Err(WebSocketError::Protocol("Invalid close code"));
+        }
+
+        let reason: &str = str::from_utf8(reason).map_err(|_: Utf8Error| WebSocketError::InvalidUtf8)?;
+
+        Ok(Some(CloseFrame {
+            code,
+            reason: reason.to_owned(),
+        }))
+    }
+
+    fn into_message(opcode: OpCode, payload: Vec<u8>) -> Result<Message, WebSocketError> {
+        match opcode {
+            OpCode::Text => String::from_utf8(payload)
+                .map(Message::Text)
+                .map_err(|_| WebSocketError::InvalidUtf8),
+            _ => Ok(Message::Binary(payload)),
+        }
+    }
+}
+
+fn is_valid_close_code(code: u16) -> bool {
+    matches!(code, 1000..=1003 | 1007..=1011 | 3000..=4999)
+}
+
+fn apply_mask(payload: &mut [u8], mask: [u8; 4]) {
+    payload
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, byte): (usize, &mut u8)| *byte ^= mask[i % 4]);
+}
+
+fn masking_key() -> Result<[u8; 4], io::Error> {
+    let mut mask: [u8; 4] = [0; 4];
+    getrandom::fill(&mut mask).map_err(|e: getrandom::Error| {
+        warn!("Failed to generate WebSocket masking key: {e}");
+        io::Error::other(e)
+    })?;
+    Ok(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{DuplexStream, duplex};
+
+    fn pair(config: WebSocketConfig) -> (WebSocket, WebSocket) {
+        let (server, client): (DuplexStream, DuplexStream) = duplex(256 * 1024);
+        (
+            WebSocket::server(Upgraded::new(server), config),
+            WebSocket::client(Upgraded::new(client), config),
+        )
+    }
+
+    fn upgrade_request(key: &str) -> Vec<u8> {
+        format!(
+            "GET /ws HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\nConnection: keep-alive, Upgrade\r\n\
+             Sec-WebSocket-Version: 13\r\nSec-WebSocket-Key: {key}\r\n\r\n"
+        )
+        .into_bytes()
+    }
+
+    #[test]
+    fn test_accept_key() {
+        assert_eq!(accept_key("dGhlIHNhbXBsZSBub25jZQ=="), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+    }
+
+    #[test]
+    fn test_upgrade_handshake() {
+        let raw: Vec<u8> = upgrade_request("dGhlIHNhbXBsZSBub25jZQ==");
+        let request: Request = Request::new(&raw).unwrap();
+        let upgrade: WebSocketUpgrade = WebSocketUpgrade::from_request(&request).unwrap();
+
+        let mut response: Response = upgrade.on_upgrade(|_: WebSocket| async {});
+
+        assert_eq!(response.status(), HttpStatus::SwitchingProtocols);
+        assert_eq!(
+            response.headers().get("sec-websocket-accept").unwrap().as_bytes(),
+            b"s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+        assert!(response.take_upgrade().is_some());
+    }
+
+    #[test]
+    fn test_upgrade_handshake_errors() {
+        let raw: &[u8] = b"GET /ws HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        let request: Request = Request::new(raw).unwrap();
+        let error: HttpError = WebSocketUpgrade::from_request(&request).err().unwrap();
+        assert_eq!(error.status, HttpStatus::UpgradeRequired);
+
+        let raw: Vec<u8> = upgrade_request("c2hvcnQ=");
+        let request: Request = Request::new(&raw).unwrap();
+        let error: HttpError = WebSocketUpgrade::from_request(&request).err().unwrap();
+        assert_eq!(error.status, HttpStatus::BadRequest);
+
+        let raw: Vec<u8> = String::from_utf8(upgrade_request("dGhlIHNhbXBsZSBub25jZQ=="))
+            .unwrap()
+            .replace("GET", "POST")
+            .into_bytes();
+        let request: Request = Request::new(&raw).unwrap();
+        let error: HttpError = WebSocketUpgrade::from_request(&request).err().unwrap();
+        assert_eq!(error.status, HttpStatus::BadRequest);
+    }
+
+    #[tokio::test]
+    async fn test_text_and_binary_round_trip() {
+        let (mut server, mut client): (WebSocket, WebSocket) = pair(WebSocketConfig::default());
+
+        client.send(Message::Text("hello".into())).await.unwrap();
+        client.send(Message::Binary(vec![0; 70_000])).await.unwrap();
+
+        assert_eq!(server.recv().await.unwrap().unwrap(), Message::Text("hello".into()));
+        assert_eq!(server.recv().await.unwrap().unwrap(), Message::Binary(vec![0; 70_000]));
+
+        server.send(Message::Text("world".into())).await.unwrap();
+        assert_eq!(client.recv().await.unwrap().unwrap(), Message::Text("world".into()));
+    }
+
+    #[tokio::test]
+    async fn test_fragmented_message_with_interleaved_ping() {
+        let (mut server, mut client): (WebSocket, WebSocket) = pair(WebSocketConfig::default());
+
+        client
+            .io
+            .write_all(&[0x01, 0x83, 0, 0, 0, 0, b'a', b'b', b'c'])
+            .await
+            .unwrap();
+        client.io.write_all(&[0x89, 0x80, 0, 0, 0, 0]).await.unwrap();
+        client
+            .io
+            .write_all(&[0x80, 0x82, 0, 0, 0, 0, b'd', b'e'])
+            .await
+            .unwrap();
+
+        assert_eq!(server.recv().await.unwrap().unwrap(), Message::Ping(Vec::new()));
+        assert_eq!(server.recv().await.unwrap().unwrap(), Message::Text("abcde".into()));
+        assert_eq!(client.recv().await.unwrap().unwrap(), Message::Pong(Vec::new()));
+    }
+
+    #[tokio::test]
+    async fn test_close_handshake() {
+        let (mut server, mut client): (WebSocket, WebSocket) = pair(WebSocketConfig::default());
+
+        client.close(1000, "bye").await.unwrap();
+
+        let expected: Message = Message::Close(Some(CloseFrame {
+            code: 1000,
+            reason: "bye".into(),
+        }));
+
+        assert_eq!(server.recv().await.unwrap().unwrap(), expected);
+        assert!(server.recv().await.is_none());
+        assert!(matches!(
+            server.send(Message::Text("late".into())).await,
+            Err(WebSocketError::Closed)
+        ));
+        assert_eq!(client.recv().await.unwrap().unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_frame_size_limit() {
+        let config: WebSocketConfig = WebSocketConfig {
+            max_frame_size: 4,
+            ..WebSocketConfig::default()
+        };
+        let (mut server, mut client): (WebSocket, WebSocket) = pair(config);
+
+        client.send(Message::Text("too long".into())).await.unwrap();
+
+        assert!(matches!(server.recv().await, Some(Err(WebSocketError::FrameTooLarge(8)))));
+        assert!(server.recv().await.is_none());
+
+        let close: Message = client.recv().await.unwrap().unwrap();
+        assert!(matches!(close, Message::Close(Some(CloseFrame { code: 1009, .. }))));
+    }
+
+    #[tokio::test]
+    async fn test_message_size_limit() {
+        let config: WebSocketConfig = WebSocketConfig {
+            max_frame_size: 4,
+            max_message_size: 6,
+        };
+        let (mut server, mut client): (WebSocket, WebSocket) = pair(config);
+
+        client
+            .io
+            .write_all(&[0x02, 0x84, 0, 0, 0, 0, 1, 2, 3, 4])
+            .await
+            .unwrap();
+        client
+            .io
+            .write_all(&[0x80, 0x84, 0, 0, 0, 0, 5, 6, 7, 8])
+            .await
+            .unwrap();
+
+        assert!(matches!(server.recv().await, Some(Err(WebSocketError::MessageTooLarge(8)))));
+
+        let config: WebSocketConfig = WebSocketConfig {
+            max_frame_size: 16,
+            max_message_size: 6,
+        };
+        let (mut server, mut client): (WebSocket, WebSocket) = pair(config);
+
+        client.send(Message::Binary(vec![0; 8])).await.unwrap();
+        assert!(matches!(server.recv().await, Some(Err(WebSocketError::MessageTooLarge(8)))));
+    }
+
+    #[tokio::test]
+    async fn test_outgoing_control_frame_limits() {
+        let (mut server, mut client): (WebSocket, WebSocket) = pair(WebSocketConfig::default());
+
+        assert!(matches!(
+            client.send(Message::Ping(vec![0; 200])).await,
+            Err(WebSocketError::Protocol(_))
+        ));
+        assert!(matches!(
+            client.send(Message::Pong(vec![0; 126])).await,
+            Err(WebSocketError::Protocol(_))
+        ));
+        assert!(matches!(
+            client.close(1000, "x".repeat(124)).await,
+            Err(WebSocketError::Protocol(_))
+        ));
+
+        for code in [999, 1005, 1006, 1015, 5000] {
+            assert!(matches!(client.close(code, "").await, Err(WebSocketError::Protocol(_))));
+        }
+
+        client.send(Message::Ping(vec![0; 125])).await.unwrap();
+        assert_eq!(server.recv().await.unwrap().unwrap(), Message::Ping(vec![0; 125]));
+
+        client.close(1000, "x".repeat(123)).await.unwrap();
+        assert!(matches!(server.recv().await, Some(Ok(Message::Close(Some(_))))));
+    }
+
+    #[test]
+    fn test_masking_keys_differ() {
+        let keys: Vec<[u8; 4]> = (0..8).map(|_| masking_key().unwrap()).collect();
+        assert!(keys.iter().any(|key: &[u8; 4]| *key != keys[0]));
+    }
+
+    #[tokio::test]
+    async fn test_protocol_violations() {
+        let (mut server, mut client): (WebSocket, WebSocket) = pair(WebSocketConfig::default());
+        client.io.write_all(&[0x81, 0x02, b'h', b'i']).await.unwrap();
+        assert!(matches!(server.recv().await, Some(Err(WebSocketError::Protocol(_)))));
+
+        let (mut server, mut client): (WebSocket, WebSocket) = pair(WebSocketConfig::default());
+        client
+            .io
+            .write_all(&[0x81, 0x82, 0, 0, 0, 0, 0xC3, 0x28])
+            .await
+            .unwrap();
+        assert!(matches!(server.recv().await, Some(Err(WebSocketError::InvalidUtf8))));
+
+        let close: Message = client.recv().await.unwrap().unwrap();
+        assert!(matches!(close, Message::Close(Some(CloseFrame { code: 1007, .. }))));
+    }
+}
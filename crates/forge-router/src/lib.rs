@@ -5,8 +5,9 @@ pub mod router;
 
 pub use error::RouterError;
 pub use handler::{AsyncResolver, Handler, IntoHandler, OutputWrapper, Result, SyncResolver};
-pub use router::Router;
+pub use router::{Endpoint, Router};
 
 pub use forge_http::HttpMethod;
 pub use forge_http::IntoResponse;
 pub use forge_http::Request;
+pub use forge_http::{WebSocket, WebSocketConfig, WebSocketUpgrade};
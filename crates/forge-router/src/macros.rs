@@ -1,5 +1,13 @@
 #[macro_export]
 macro_rules! route {
+    ($router:ident, $method:literal, $path:literal, $($t:tt)*) => {
+        $crate::route!(
+            $router,
+            $method.parse::<$crate::HttpMethod>().expect("Invalid HTTP method"),
+            $path,
+            $($t)*
+        )
+    };
     ($router:ident, $method:expr, $path:literal, $handler:expr) => {{
         #[allow(unused_imports)]
         use $crate::{AsyncResolver, IntoResponse, SyncResolver};
@@ -10,12 +18,28 @@ macro_rules! route {
 
         $router.register($method, $path, wrapper)
     }};
+    ($router:ident, $method:expr, $path:literal, $handler:expr, $limits:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::{AsyncResolver, IntoResponse, SyncResolver};
+
+        fn wrapper<'a>(req: $crate::Request<'a>) -> $crate::Result<'a> {
+            Box::pin(async move { $crate::OutputWrapper(Some($handler(req))).resolve().await })
+        }
+
+        $router.register_with_limits($method, $path, wrapper, $limits)
+    }};
 }
 
 #[macro_export]
 macro_rules! routes {
-    ($router:ident, { $($method:ident $path:literal => $handler:expr),* $(,)? }) => {
-        $($crate::$method!($router, $path, $handler);)*
+    (@route $router:ident, $method:literal, $path:literal, $handler:expr) => {
+        $crate::route!($router, $method, $path, $handler)
+    };
+    (@route $router:ident, $method:ident, $path:literal, $handler:expr) => {
+        $crate::$method!($router, $path, $handler)
+    };
+    ($router:ident, { $($method:tt $path:literal => $handler:expr),* $(,)? }) => {
+        $($crate::routes!(@route $router, $method, $path, $handler);)*
     };
 }
 
@@ -42,3 +66,25 @@ macro_rules! options { ($r:ident, $p:literal, $($t:tt)*) => { $crate::route!($r,
 
 #[macro_export]
 macro_rules! trace { ($r:ident, $p:literal, $($t:tt)*) => { $crate::route!($r, $crate::HttpMethod::TRACE, $p, $($t)*) } }
+
+#[macro_export]
+macro_rules! connect { ($r:ident, $p:literal, $($t:tt)*) => { $crate::route!($r, $crate::HttpMethod::CONNECT, $p, $($t)*) } }
+
+#[macro_export]
+macro_rules! websocket {
+    ($router:ident, $path:literal, $handler:expr) => {
+        $crate::websocket!($router, $path, $handler, $crate::WebSocketConfig::default())
+    };
+    ($router:ident, $path:literal, $handler:expr, $config:expr) => {{
+        fn wrapper<'a>(req: $crate::Request<'a>) -> $crate::Result<'a> {
+            Box::pin(async move {
+                match $crate::WebSocketUpgrade::from_request(&req) {
+                    Ok(upgrade) => upgrade.with_config($config).on_upgrade($handler),
+                    Err(e) => e.into(),
+                }
+            })
+        }
+
+        $router.register($crate::HttpMethod::GET, $path, wrapper)
+    }};
+}
@@ -2,12 +2,12 @@ use std::collections::HashMap;
 
 use super::RouterError;
 use super::{Handler, IntoHandler};
-use forge_http::HttpMethod;
+use forge_http::{HttpMethod, Limits};
 use forge_utils::{PathMatch, PathTree, Segment};
 use tracing::{debug, trace};
 
 type Path = &'static str;
-type Routes = HashMap<HttpMethod, PathTree<Handler>>; // TODO: Add support to dynamic routes (wildcards)
+type Routes = HashMap<HttpMethod, PathTree<Endpoint>>; // TODO: Add support to dynamic routes (wildcards)
 
 const ROUTER_RULES: (char, char) = ('/', ':');
 
@@ -15,6 +15,12 @@ pub struct Route {
     pub path: Path,
     pub handler: Handler,
     pub method: HttpMethod,
+    pub limits: Option<Limits>,
+}
+
+pub struct Endpoint {
+    pub handler: Handler,
+    pub limits: Option<Limits>,
 }
 
 pub struct Router {
@@ -38,23 +44,42 @@ impl Router {
             path,
             method,
             handler: handler.into_handler(),
+            limits: None,
+        })
+        .expect("Fatal error registering route");
+    }
+
+    pub fn register_with_limits<T: IntoHandler>(
+        &mut self,
+        method: HttpMethod,
+        path: &'static str,
+        handler: T,
+        limits: Limits,
+    ) {
+        self.add_route(Route {
+            path,
+            method,
+            handler: handler.into_handler(),
+            limits: Some(limits),
         })
         .expect("Fatal error registering route");
     }
 
-    pub fn get_route<'a, 'b>(&'a self, path: &'b str, method: &HttpMethod) -> Option<PathMatch<'a, 'b, Handler>> {
+    pub fn get_route<'a, 'b>(&'a self, path: &'b str, method: &HttpMethod) -> Option<PathMatch<'a, 'b, Endpoint>> {
         trace!("Looking up route for {method} {path}");
-        let path_tree: &PathTree<Handler> = self.routes.get(method)?;
+        let path_tree: &PathTree<Endpoint> = self.routes.get(method)?;
         path_tree.find(Self::sanitize_path(path))
     }
 
     fn add_route(&mut self, route: Route) -> Result<(), RouterError> {
-        let path_tree: &mut PathTree<Handler> = self.routes.entry(route.method).or_default();
+        let path_tree: &mut PathTree<Endpoint> = self.routes.entry(route.method.clone()).or_default();
 
-        if path_tree
-            .insert(Self::parse_to_segment(route.path), route.handler)
-            .is_some()
-        {
+        let endpoint: Endpoint = Endpoint {
+            handler: route.handler,
+            limits: route.limits,
+        };
+
+        if path_tree.insert(Self::parse_to_segment(route.path), endpoint).is_some() {
             return Err(RouterError::DuplicateRoute(Self::fmt_route(&route.method, route.path)));
         };
 
@@ -87,7 +112,7 @@ impl Router {
 mod tests {
     use super::*;
     use crate::get;
-    use forge_http::{HttpStatus, Request, Response};
+    use forge_http::{HttpStatus, Request, Response, WebSocket, WebSocketConfig};
 
     fn dummy_handler(_: Request) -> Response {
         Response::new(HttpStatus::Ok)
@@ -98,10 +123,10 @@ mod tests {
         let mut router: Router = Router::new();
         get!(router, "/ping", dummy_handler);
 
-        let result: Option<PathMatch<Handler>> = router.get_route("/ping", &HttpMethod::GET);
+        let result: Option<PathMatch<Endpoint>> = router.get_route("/ping", &HttpMethod::GET);
         assert!(result.is_some());
 
-        let match_data: PathMatch<Handler> = result.unwrap();
+        let match_data: PathMatch<Endpoint> = result.unwrap();
         assert!(match_data.params.is_empty());
     }
 
@@ -110,7 +135,7 @@ mod tests {
         let mut router: Router = Router::new();
         get!(router, "/ping", dummy_handler);
 
-        let result: Option<PathMatch<Handler>> = router.get_route("/pong", &HttpMethod::GET);
+        let result: Option<PathMatch<Endpoint>> = router.get_route("/pong", &HttpMethod::GET);
         assert!(result.is_none());
     }
 
@@ -119,10 +144,10 @@ mod tests {
         let mut router: Router = Router::new();
         get!(router, "/data", dummy_handler);
 
-        let result_get: Option<PathMatch<Handler>> = router.get_route("/data", &HttpMethod::GET);
+        let result_get: Option<PathMatch<Endpoint>> = router.get_route("/data", &HttpMethod::GET);
         assert!(result_get.is_some());
 
-        let result_post: Option<PathMatch<Handler>> = router.get_route("/data", &HttpMethod::POST);
+        let result_post: Option<PathMatch<Endpoint>> = router.get_route("/data", &HttpMethod::POST);
         assert!(result_post.is_none());
     }
 
@@ -131,10 +156,10 @@ mod tests {
         let mut router: Router = Router::new();
         get!(router, "/users/:id", dummy_handler);
 
-        let result: Option<PathMatch<Handler>> = router.get_route("/users/123", &HttpMethod::GET);
+        let result: Option<PathMatch<Endpoint>> = router.get_route("/users/123", &HttpMethod::GET);
         assert!(result.is_some());
 
-        let match_data: PathMatch<Handler> = result.unwrap();
+        let match_data: PathMatch<Endpoint> = result.unwrap();
         assert_eq!(match_data.params.len(), 1);
         assert_eq!(match_data.params[0], ("id", "123"));
     }
@@ -144,10 +169,10 @@ mod tests {
         let mut router: Router = Router::new();
         get!(router, "/store/:store_id/customer/:customer_id", dummy_handler);
 
-        let result: Option<PathMatch<Handler>> = router.get_route("/store/99/customer/500", &HttpMethod::GET);
+        let result: Option<PathMatch<Endpoint>> = router.get_route("/store/99/customer/500", &HttpMethod::GET);
         assert!(result.is_some());
 
-        let match_data: PathMatch<Handler> = result.unwrap();
+        let match_data: PathMatch<Endpoint> = result.unwrap();
         assert_eq!(match_data.params.len(), 2);
 
         let has_store: bool = match_data.params.contains(&("store_id", "99"));
@@ -170,7 +195,7 @@ mod tests {
         ];
 
         for path in paths_to_test {
-            let result: Option<PathMatch<Handler>> = router.get_route(path, &HttpMethod::GET);
+            let result: Option<PathMatch<Endpoint>> = router.get_route(path, &HttpMethod::GET);
             assert!(result.is_some(), "Failed to match path: {path}");
         }
     }
@@ -180,10 +205,10 @@ mod tests {
         let mut router: Router = Router::new();
         get!(router, "/a/b/c/d", dummy_handler);
 
-        let result: Option<PathMatch<Handler>> = router.get_route("/a/b/c/d", &HttpMethod::GET);
+        let result: Option<PathMatch<Endpoint>> = router.get_route("/a/b/c/d", &HttpMethod::GET);
         assert!(result.is_some());
 
-        let partial: Option<PathMatch<Handler>> = router.get_route("/a/b/c", &HttpMethod::GET);
+        let partial: Option<PathMatch<Endpoint>> = router.get_route("/a/b/c", &HttpMethod::GET);
         assert!(partial.is_none());
     }
 
@@ -192,11 +217,11 @@ mod tests {
         let mut router: Router = Router::new();
         get!(router, "/files/:type/recent", dummy_handler);
 
-        let result: Option<PathMatch<Handler>> = router.get_route("/files/images/recent", &HttpMethod::GET);
+        let result: Option<PathMatch<Endpoint>> = router.get_route("/files/images/recent", &HttpMethod::GET);
         assert!(result.is_some());
         assert_eq!(result.unwrap().params[0], ("type", "images"));
 
-        let result_fail: Option<PathMatch<Handler>> = router.get_route("/files/images/old", &HttpMethod::GET);
+        let result_fail: Option<PathMatch<Endpoint>> = router.get_route("/files/images/old", &HttpMethod::GET);
         assert!(result_fail.is_none());
     }
 
@@ -208,6 +233,86 @@ mod tests {
         get!(router, "/duplicate", dummy_handler);
     }
 
+    #[test]
+    fn test_webdav_and_extension_routes() {
+        let mut router: Router = Router::new();
+
+        crate::route!(router, "PROPFIND", "/files/:name", dummy_handler);
+        crate::route!(router, HttpMethod::MOVE, "/files/:name", dummy_handler);
+        crate::route!(router, "PURGE", "/cache", dummy_handler);
+
+        let propfind: Option<PathMatch<Endpoint>> = router.get_route("/files/a.txt", &HttpMethod::PROPFIND);
+        assert_eq!(propfind.unwrap().params[0], ("name", "a.txt"));
+
+        let moved: Option<PathMatch<Endpoint>> = router.get_route("/files/a.txt", &HttpMethod::MOVE);
+        assert!(moved.is_some());
+
+        let purge: HttpMethod = "PURGE".parse().unwrap();
+        assert!(router.get_route("/cache", &purge).is_some());
+        assert!(router.get_route("/cache", &HttpMethod::GET).is_none());
+    }
+
+    #[test]
+    fn test_routes_macro_with_webdav_methods() {
+        let mut router: Router = Router::new();
+
+        crate::routes!(router, {
+            get "/collections/:id" => dummy_handler,
+            "MKCOL" "/collections/:id" => dummy_handler,
+            "MOVE" "/collections/:id" => dummy_handler,
+        });
+
+        assert!(router.get_route("/collections/1", &HttpMethod::GET).is_some());
+        assert!(router.get_route("/collections/1", &HttpMethod::MKCOL).is_some());
+        assert!(router.get_route("/collections/1", &HttpMethod::MOVE).is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid HTTP method")]
+    fn test_invalid_extension_method_panics() {
+        let mut router: Router = Router::new();
+        crate::route!(router, "NOT A TOKEN", "/cache", dummy_handler);
+    }
+
+    #[test]
+    fn test_route_limits() {
+        let mut router: Router = Router::new();
+        let limits: Limits = Limits {
+            max_body_size: 64,
+            ..Default::default()
+        };
+
+        crate::post!(router, "/small", dummy_handler);
+        crate::post!(router, "/upload", dummy_handler, limits);
+
+        let small: PathMatch<Endpoint> = router.get_route("/small", &HttpMethod::POST).unwrap();
+        assert!(small.value.limits.is_none());
+
+        let upload: PathMatch<Endpoint> = router.get_route("/upload", &HttpMethod::POST).unwrap();
+        assert_eq!(upload.value.limits, Some(limits));
+    }
+
+    #[test]
+    fn test_websocket_route() {
+        async fn echo(_: WebSocket) {}
+
+        let mut router: Router = Router::new();
+        crate::websocket!(router, "/ws", echo);
+        crate::websocket!(
+            router,
+            "/ws/:room",
+            echo,
+            WebSocketConfig {
+                max_frame_size: 1024,
+                max_message_size: 4096,
+            }
+        );
+
+        assert!(router.get_route("/ws", &HttpMethod::GET).is_some());
+        assert!(router.get_route("/ws/lobby", &HttpMethod::GET).is_some());
+        assert!(router.get_route("/ws", &HttpMethod::POST).is_none());
+    }
+
     #[test]
     fn test_overlapping_routes_precedence() {
         let mut router: Router = Router::new();
@@ -215,11 +320,11 @@ mod tests {
         get!(router, "/users/all", dummy_handler);
         get!(router, "/users/:id", dummy_handler);
 
-        let exact_match: Option<PathMatch<Handler>> = router.get_route("/users/all", &HttpMethod::GET);
+        let exact_match: Option<PathMatch<Endpoint>> = router.get_route("/users/all", &HttpMethod::GET);
         assert!(exact_match.is_some());
         assert!(exact_match.unwrap().params.is_empty());
 
-        let param_match: Option<PathMatch<Handler>> = router.get_route("/users/123", &HttpMethod::GET);
+        let param_match: Option<PathMatch<Endpoint>> = router.get_route("/users/123", &HttpMethod::GET);
         assert!(param_match.is_some());
         assert_eq!(param_match.unwrap().params[0], ("id", "123"));
     }
@@ -0,0 +1,263 @@
+use std::future::poll_fn;
+use std::io::{Error, ErrorKind};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::task::Context;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use super::socket::{BoundSocket, ListenSocket};
+use super::stream::StreamAcceptor;
+use super::{Connection, ListenerOptions, OverloadPolicy, Stream, shutdown};
+use forge_http::{HttpError, HttpStatus, Response};
+use forge_router::Router;
+use tokio::io::{self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::runtime::{Builder, EnterGuard, Handle, Runtime};
+use tokio::sync::watch::Receiver;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::task::JoinSet;
+use tokio::time::{Instant, sleep_until, timeout};
+use tracing::{debug, error, info, warn};
+
+#[cfg(feature = "http2")]
+use super::http2::{self, Http2Connection};
+
+const MIN_ACCEPT_BACKOFF: Duration = Duration::from_millis(5);
+const MAX_ACCEPT_BACKOFF: Duration = Duration::from_secs(1);
+const REJECT_DRAIN_LIMIT: u64 = 64 * 1024;
+const REJECT_TIMEOUT: Duration = Duration::from_secs(1);
+pub(crate) const MAX_PENDING_REJECTIONS: usize = 64;
+
+pub(crate) struct AcceptLoop {
+    pub(crate) index: usize,
+    pub(crate) router: Arc<Router>,
+    pub(crate) options: Arc<ListenerOptions>,
+    pub(crate) acceptor: StreamAcceptor,
+    pub(crate) semaphore: Arc<Semaphore>,
+    pub(crate) rejections: Arc<Semaphore>,
+    pub(crate) shutdown: Receiver<bool>,
+    pub(crate) handoff: Option<Handle>,
+}
+
+impl AcceptLoop {
+    pub(crate) fn spawn(self, sockets: Vec<ListenSocket>, loops: &mut JoinSet<()>) -> Result<(), Error> {
+        let sockets: Vec<BoundSocket> = Self::register(sockets)?;
+        loops.spawn(self.run(sockets));
+        Ok(())
+    }
+
+    pub(crate) fn spawn_pinned(mut self, sockets: Vec<ListenSocket>) -> Result<JoinHandle<()>, Error> {
+        self.handoff = Some(Handle::current());
+        let runtime: Runtime = Builder::new_current_thread().enable_all().build()?;
+
+        let sockets: Vec<BoundSocket> = {
+            let _guard: EnterGuard = runtime.enter();
+            Self::register(sockets)?
+        };
+
+        thread::Builder::new()
+            .name(format!("forge-acceptor-{}", self.index))
+            .spawn(move || runtime.block_on(self.run(sockets)))
+    }
+
+    fn register(sockets: Vec<ListenSocket>) -> Result<Vec<BoundSocket>, Error> {
+        sockets.into_iter().map(ListenSocket::into_async).collect()
+    }
+
+    async fn run(self, sockets: Vec<BoundSocket>) {
+        let mut connections: JoinSet<()> = JoinSet::new();
+        let mut shutdown: Option<Receiver<bool>> = Some(self.shutdown.clone());
+        let mut backoff: Duration = Duration::ZERO;
+        let mut cursor: usize = 0;
+        let mut paused: bool = false;
+        let mut waiting: Option<Stream> = None;
+        let mut next_accept_at: Option<Instant> = None;
+
+        debug!("Acceptor {} listening on {} sockets", self.index, sockets.len());
+
+        loop {
+            tokio::select! {
+                result = Self::accept_any(&sockets, &mut cursor), if waiting.is_none() && next_accept_at.is_none() => match result {
+                    Ok(stream) => {
+                        backoff = Duration::ZERO;
+
+                        match (self.semaphore.clone().try_acquire_owned(), self.options.overload_policy) {
+                            (Ok(permit), _) => {
+                                if paused {
+                                    info!("Connection slot available, resuming accept");
+                                    paused = false;
+                                }
+
+                                self.spawn_connection(&mut connections, stream, permit);
+                            }
+                            (Err(_), OverloadPolicy::Backpressure) => {
+                                if !paused {
+                                    warn!("Connection limit of {} reached, pausing accept", self.options.max_connections);
+                                    paused = true;
+                                }
+
+                                waiting = Some(stream);
+                            }
+                            (Err(_), OverloadPolicy::Reject) => self.reject_connection(&mut connections, stream),
+                        }
+                    }
+                    Err(e) if Self::is_connection_error(&e) => {
+                        debug!("Accepted connection failed before it could be served: {e}");
+                    }
+                    Err(e) => {
+                        backoff = (backoff * 2).clamp(MIN_ACCEPT_BACKOFF, MAX_ACCEPT_BACKOFF);
+                        error!("Failed to accept connection: {e}, retrying in {backoff:?}");
+                        next_accept_at = Some(Instant::now() + backoff);
+                    }
+                },
+                _ = sleep_until(next_accept_at.unwrap_or_else(Instant::now)), if next_accept_at.is_some() => {
+                    next_accept_at = None;
+                }
+                Ok(permit) = self.semaphore.clone().acquire_owned(), if waiting.is_some() => {
+                    if let Some(stream) = waiting.take() {
+                        self.spawn_connection(&mut connections, stream, permit);
+                    }
+                }
+                Some(_) = connections.join_next(), if !connections.is_empty() => {}
+                _ = shutdown::triggered(&mut shutdown) => break,
+            }
+        }
+
+        drop(sockets);
+        info!(
+            "Acceptor {} stopped accepting connections, draining {} open connections",
+            self.index,
+            connections.len()
+        );
+
+        let grace_period: Duration = self.options.shutdown_grace_period;
+
+        if timeout(grace_period, async { while connections.join_next().await.is_some() {} })
+            .await
+            .is_err()
+        {
+            warn!(
+                "Grace period of {grace_period:?} elapsed, closing {} connections",
+                connections.len()
+            );
+            connections.shutdown().await;
+        }
+    }
+
+    fn spawn_connection(&self, connections: &mut JoinSet<()>, stream: Stream, permit: OwnedSemaphorePermit) {
+        let router: Arc<Router> = self.router.clone();
+        let options: Arc<ListenerOptions> = self.options.clone();
+        let shutdown: Receiver<bool> = self.shutdown.clone();
+        let acceptor: StreamAcceptor = self.acceptor.clone();
+
+        let handoff: bool = self.handoff.is_some();
+
+        let connection = async move {
+            let stream: Result<Stream, Error> = match handoff {
+                true => stream.reregister(),
+                false => Ok(stream),
+            };
+
+            let established: Result<Stream, Error> = match stream {
+                Ok(stream) => acceptor.accept(stream, options.timeouts.header_read).await,
+                Err(e) => Err(e),
+            };
+
+            match established {
+                Ok(stream) => Self::serve(router, stream, &options, shutdown).await,
+                Err(e) => debug!("Failed to establish connection: {e}"),
+            }
+
+            drop(permit);
+        };
+
+        match &self.handoff {
+            Some(handle) => connections.spawn_on(connection, handle),
+            None => connections.spawn(connection),
+        };
+    }
+
+    fn reject_connection(&self, connections: &mut JoinSet<()>, stream: Stream) {
+        warn!(
+            "Connection limit of {} reached, rejecting connection",
+            self.options.max_connections
+        );
+
+        if self.acceptor.requires_handshake(&stream) {
+            debug!("Closing rejected connection before TLS handshake");
+        } else if let Ok(permit) = self.rejections.clone().try_acquire_owned() {
+            connections.spawn(Self::reject(stream, permit));
+        } else {
+            debug!("Too many pending rejections, dropping connection");
+        }
+    }
+
+    async fn accept_any(sockets: &[BoundSocket], cursor: &mut usize) -> Result<Stream, Error> {
+        poll_fn(|cx: &mut Context<'_>| BoundSocket::poll_accept_any(sockets, cursor, cx)).await
+    }
+
+    async fn serve(router: Arc<Router>, stream: Stream, options: &ListenerOptions, shutdown: Receiver<bool>) {
+        let peer_addr: Option<SocketAddr> = stream.peer_addr().ok();
+
+        #[cfg(feature = "http2")]
+        if stream.alpn_protocol() == Some(http2::ALPN_PROTOCOL) {
+            debug!("Negotiated HTTP/2 via ALPN with {peer_addr:?}");
+            return Http2Connection::new(router, stream, options)
+                .with_shutdown(shutdown)
+                .serve()
+                .await;
+        }
+
+        match stream {
+            Stream::Plain(stream) => Self::serve_http1(router, stream, peer_addr, options, shutdown).await,
+            #[cfg(any(unix, feature = "tls"))]
+            stream => Self::serve_http1(router, stream, peer_addr, options, shutdown).await,
+        }
+    }
+
+    async fn serve_http1<S>(
+        router: Arc<Router>,
+        stream: S,
+        peer_addr: Option<SocketAddr>,
+        options: &ListenerOptions,
+        shutdown: Receiver<bool>,
+    ) where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let connection: Connection<S> = Connection::new(router, stream, options);
+        connection
+            .with_peer_addr(peer_addr)
+            .with_shutdown(shutdown)
+            .serve()
+            .await;
+    }
+
+    async fn reject(mut stream: Stream, permit: OwnedSemaphorePermit) {
+        let mut response: Response = Response::new(HttpStatus::ServiceUnavailable);
+        response.set_keep_alive(false);
+
+        let rejected = async {
+            response
+                .send(&mut stream)
+                .await
+                .map_err(|e: HttpError| Error::other(e.message))?;
+            stream.shutdown().await?;
+            io::copy(&mut (&mut stream).take(REJECT_DRAIN_LIMIT), &mut io::sink()).await
+        };
+
+        match timeout(REJECT_TIMEOUT, rejected).await {
+            Ok(Ok(_)) => debug!("Rejected connection with 503"),
+            Ok(Err(e)) => debug!("Failed to reject connection cleanly: {e}"),
+            Err(_) => debug!("Timed out rejecting connection after {REJECT_TIMEOUT:?}"),
+        }
+
+        drop(permit);
+    }
+
+    fn is_connection_error(e: &Error) -> bool {
+        matches!(
+            e.kind(),
+            ErrorKind::ConnectionAborted | ErrorKind::ConnectionRefused | ErrorKind::ConnectionReset
+        )
+    }
+}
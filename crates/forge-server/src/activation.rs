@@ -0,0 +1,157 @@
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::ops::Range;
+use std::os::fd::{BorrowedFd, FromRawFd, RawFd};
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::socket::ListenSocket;
+use socket2::{SockRef, Socket};
+use tracing::{debug, warn};
+
+const LISTEN_FDS_START: RawFd = 3;
+const MAX_LISTEN_FDS: RawFd = 1024;
+const LISTEN_PID: &str = "LISTEN_PID";
+const LISTEN_FDS: &str = "LISTEN_FDS";
+
+static CLAIMED: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn listen_fds() -> Result<Vec<ListenSocket>, Error> {
+    let pid: Option<String> = env::var(LISTEN_PID).ok();
+    let fds: Option<String> = env::var(LISTEN_FDS).ok();
+    let count: RawFd = fd_count(pid.as_deref(), fds.as_deref(), process::id())?;
+
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+
+    if CLAIMED.load(Ordering::SeqCst) {
+        warn!("Socket activation file descriptors were already claimed");
+        return Ok(Vec::new());
+    }
+
+    let fds: Range<RawFd> = LISTEN_FDS_START..LISTEN_FDS_START + count;
+    let kinds: Vec<bool> = fds
+        .clone()
+        .map(|fd: RawFd| {
+            // SAFETY: the service manager keeps these descriptors open for the lifetime of the
+            // process, and they are only borrowed here until ownership is claimed below.
+            let borrowed: BorrowedFd<'_> = unsafe { BorrowedFd::borrow_raw(fd) };
+            ListenSocket::inspect(&SockRef::from(&borrowed))
+        })
+        .collect::<Result<Vec<bool>, Error>>()?;
+
+    if CLAIMED.swap(true, Ordering::SeqCst) {
+        warn!("Socket activation file descriptors were already claimed");
+        return Ok(Vec::new());
+    }
+
+    debug!("Adopting {count} file descriptors from socket activation");
+
+    let sockets: Vec<ListenSocket> = fds
+        .zip(kinds)
+        .map(|(fd, unix): (RawFd, bool)| {
+            // SAFETY: the service manager passes ownership of these descriptors to this process,
+            // and `CLAIMED` ensures they are only wrapped once.
+            let socket: Socket = unsafe { Socket::from_raw_fd(fd) };
+            ListenSocket::adopt(socket, unix)
+        })
+        .collect();
+
+    Ok(sockets)
+}
+
+fn fd_count(pid: Option<&str>, fds: Option<&str>, current_pid: u32) -> Result<RawFd, Error> {
+    let (Some(pid), Some(fds)) = (pid, fds) else {
+        return Ok(0);
+    };
+
+    let pid: u32 = pid.trim().parse().map_err(|_| {
+        warn!("Invalid {LISTEN_PID} value '{pid}'");
+        Error::new(ErrorKind::InvalidInput, format!("Invalid {LISTEN_PID} value"))
+    })?;
+
+    if pid != current_pid {
+        debug!("Ignoring socket activation for process {pid}");
+        return Ok(0);
+    }
+
+    let count: RawFd = fds
+        .trim()
+        .parse()
+        .ok()
+        .filter(|count: &RawFd| (0..=MAX_LISTEN_FDS).contains(count))
+        .ok_or_else(|| {
+            warn!("Invalid {LISTEN_FDS} value '{fds}'");
+            Error::new(ErrorKind::InvalidInput, format!("Invalid {LISTEN_FDS} value"))
+        })?;
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use socket2::{Domain, Type};
+    use std::net::{Ipv4Addr, SocketAddr};
+
+    #[test]
+    fn test_fd_count_without_environment() {
+        assert_eq!(fd_count(None, None, 42).unwrap(), 0);
+        assert_eq!(fd_count(Some("42"), None, 42).unwrap(), 0);
+        assert_eq!(fd_count(None, Some("2"), 42).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_fd_count_for_current_process() {
+        assert_eq!(fd_count(Some("42"), Some("2"), 42).unwrap(), 2);
+        assert_eq!(fd_count(Some(" 42 "), Some("0"), 42).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_fd_count_ignores_other_process() {
+        assert_eq!(fd_count(Some("41"), Some("2"), 42).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_inspect_requires_listening_stream_socket() {
+        let socket: Socket = Socket::new(Domain::IPV4, Type::STREAM, None).unwrap();
+        socket.bind(&SocketAddr::from((Ipv4Addr::LOCALHOST, 0)).into()).unwrap();
+        assert_eq!(ListenSocket::inspect(&socket).unwrap_err().kind(), ErrorKind::InvalidInput);
+
+        socket.listen(1).unwrap();
+        assert!(!ListenSocket::inspect(&socket).unwrap());
+
+        let datagram: Socket = Socket::new(Domain::IPV4, Type::DGRAM, None).unwrap();
+        assert_eq!(ListenSocket::inspect(&datagram).unwrap_err().kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_fd_count_rejects_excessive_counts() {
+        assert_eq!(fd_count(Some("42"), Some("1024"), 42).unwrap(), 1024);
+        assert_eq!(
+            fd_count(Some("42"), Some("1025"), 42).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            fd_count(Some("42"), Some("2147483647"), 42).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+    }
+
+    #[test]
+    fn test_fd_count_rejects_invalid_values() {
+        assert_eq!(
+            fd_count(Some("abc"), Some("2"), 42).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            fd_count(Some("42"), Some("-1"), 42).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            fd_count(Some("42"), Some("many"), 42).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+    }
+}
@@ -1,59 +1,353 @@
+use std::borrow::Cow;
 use std::io::Error;
-use std::str::Utf8Error;
+use std::mem;
+use std::str;
 use std::sync::Arc;
+use std::time::Duration;
 use std::{io::ErrorKind, net::SocketAddr};
 
-use super::ListenerError;
-use forge_http::{HttpError, HttpStatus, Request, Response};
-use forge_router::{Handler, Router};
+#[cfg(feature = "http2")]
+use super::http2::{self, Http2Connection, Http2Options};
+use super::stream::Rewind;
+use super::{ListenerError, ListenerOptions, Stream, Timeouts, shutdown};
+use forge_http::{
+    ChunkedDecoder, HttpError, HttpStatus, HttpVersion, Limits, OnUpgrade, ParseOptions, Request, Response, Upgraded,
+};
+use forge_router::{Endpoint, Router};
 use forge_utils::PathMatch;
-use tokio::io::AsyncReadExt;
-use tokio::net::TcpStream;
-use tracing::{debug, warn};
+use tokio::io::{self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, Take};
+use tokio::sync::watch::Receiver;
+use tokio::time::{Instant, timeout, timeout_at};
+use tracing::{debug, trace, warn};
 
 const BUFFER_SIZE: usize = 4096;
+const LINGER_LIMIT: u64 = 64 * 1024;
+const LINGER_TIMEOUT: Duration = Duration::from_secs(2);
 
-pub struct Connection {
+pub struct Connection<S = Stream> {
     pub router: Arc<Router>,
-    pub stream: TcpStream,
+    pub stream: S,
+    pub parse_options: ParseOptions,
+    pub limits: Limits,
+    pub max_requests: usize,
+    pub timeouts: Timeouts,
+    #[cfg(feature = "http2")]
+    pub http2: Http2Options,
+    peer_addr: Option<SocketAddr>,
+    version: HttpVersion,
+    requests_served: usize,
+    shutdown: Option<Receiver<bool>>,
+    upgrade: Option<OnUpgrade>,
+    buffer: Vec<u8>,
+    pending: Vec<u8>,
 }
 
-impl Connection {
-    pub async fn process_request(&mut self) -> Result<(), ListenerError> {
-        let peer_addr: Option<SocketAddr> = self.stream.peer_addr().ok();
+impl<S> Connection<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    pub fn new(router: Arc<Router>, stream: S, options: &ListenerOptions) -> Self {
+        Self {
+            router,
+            stream,
+            parse_options: options.parse_options,
+            limits: options.limits,
+            max_requests: options.max_requests_per_connection,
+            timeouts: options.timeouts,
+            #[cfg(feature = "http2")]
+            http2: options.http2,
+            peer_addr: None,
+            version: HttpVersion::default(),
+            requests_served: 0,
+            shutdown: None,
+            upgrade: None,
+            buffer: Vec::with_capacity(BUFFER_SIZE),
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn with_shutdown(mut self, shutdown: Receiver<bool>) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    pub fn with_peer_addr(mut self, peer_addr: Option<SocketAddr>) -> Self {
+        self.peer_addr = peer_addr;
+        self
+    }
+
+    pub async fn serve(mut self) {
+        let mut linger: bool = false;
+
+        loop {
+            match self.process_request().await {
+                Ok(true) => continue,
+                Ok(false) => break,
+                Err(ListenerError::ConnectionClosed) => break,
+                #[cfg(feature = "http2")]
+                Err(ListenerError::Http2Preface) => return self.serve_http2().await,
+                Err(ListenerError::Http(e)) => {
+                    let mut response: Response = Response::new(e.status);
+                    response.set_version(self.version);
+                    response.set_keep_alive(false);
+
+                    if let Err(e) = self.write_response(&response).await {
+                        debug!("Failed to send error response: {e}");
+                    }
+
+                    linger = true;
+                    break;
+                }
+            }
+        }
+
+        if let Some(on_upgrade) = self.upgrade.take() {
+            return self.serve_upgrade(on_upgrade).await;
+        }
+
+        if let Ok(Err(e)) = timeout(self.timeouts.write, self.stream.shutdown()).await {
+            trace!("Failed to shut down stream cleanly: {e}");
+        }
+
+        if linger {
+            self.discard_unread().await;
+        }
+
+        debug!("Closing connection after {} requests", self.requests_served);
+    }
+
+    pub async fn process_request(&mut self) -> Result<bool, ListenerError> {
+        let mut buffer: Vec<u8> = mem::take(&mut self.buffer);
+
+        let result: Result<bool, ListenerError> = match self.handle_request(&mut buffer).await {
+            Ok((keep_alive, consumed)) => {
+                buffer.drain(..consumed);
+                buffer.append(&mut self.pending);
+
+                if !buffer.is_empty() {
+                    trace!("Carrying {} pipelined bytes over to the next request", buffer.len());
+                }
+
+                Ok(keep_alive)
+            }
+            #[cfg(feature = "http2")]
+            Err(ListenerError::Http2Preface) => Err(ListenerError::Http2Preface),
+            Err(e) => {
+                self.version = Self::sniff_version(&buffer);
+                buffer.clear();
+                Err(e)
+            }
+        };
+
+        self.buffer = buffer;
+        result
+    }
+
+    fn sniff_version(buffer: &[u8]) -> HttpVersion {
+        let Some(line_end) = buffer.iter().position(|byte: &u8| *byte == b'\n') else {
+            return HttpVersion::default();
+        };
+
+        str::from_utf8(&buffer[..line_end])
+            .ok()
+            .and_then(|line: &str| line.trim_end().rsplit(' ').next())
+            .and_then(|version: &str| version.parse::<HttpVersion>().ok())
+            .unwrap_or_default()
+    }
+
+    async fn handle_request(&mut self, buffer: &mut Vec<u8>) -> Result<(bool, usize), ListenerError> {
+        let peer_addr: Option<SocketAddr> = self.peer_addr;
         debug!("Processing connection from: {peer_addr:?}");
 
-        let mut buffer: [u8; BUFFER_SIZE] = [0; BUFFER_SIZE]; // TODO: Dynamic Buffer
-        let bytes_read: usize = self.read_request_bytes(&mut buffer).await?;
-        let raw_bytes: &[u8] = &buffer[..bytes_read];
+        self.read_head(buffer).await?;
 
-        let raw_request: &str = str::from_utf8(raw_bytes).map_err(|e: Utf8Error| {
-            warn!("Invalid UTF-8 sequence from {peer_addr:?}: {e}");
-            HttpError::new(HttpStatus::BadRequest, format!("Invalid UTF-8 sequence: {e}"))
-        })?;
+        #[cfg(feature = "http2")]
+        if self.requests_served == 0 && self.http2.prior_knowledge && buffer.starts_with(http2::PREFACE_HEAD) {
+            debug!("Received HTTP/2 connection preface from {peer_addr:?}");
+            return Err(ListenerError::Http2Preface);
+        }
 
-        let mut request: Request = Request::new(raw_request).inspect_err(|e: &HttpError| {
-            warn!("Failed to parse request from {peer_addr:?}: {e}");
-        })?;
+        let raw_bytes: &[u8] = buffer;
 
-        let route: PathMatch<Handler> = self.router.get_route(request.path, &request.method).ok_or_else(|| {
+        let (mut request, head_length): (Request, usize) =
+            Request::parse_with(raw_bytes, &self.parse_options, &self.limits).inspect_err(|e: &HttpError| {
+                warn!("Failed to parse request from {peer_addr:?}: {e}");
+            })?;
+
+        let router: Arc<Router> = self.router.clone();
+        let path: Cow<str> = request.path.clone();
+        let route: PathMatch<Endpoint> = router.get_route(&path, &request.method).ok_or_else(|| {
             warn!("404 Not Found: [{}] \"{}\"", request.method, request.path);
             HttpError::new(HttpStatus::NotFound, "The requested resource could not be found")
         })?;
 
+        let limits: Limits = match route.value.limits {
+            Some(route_limits) => {
+                let limits: Limits = route_limits.within(&self.limits);
+                limits.check_request(&request, head_length)?;
+                limits
+            }
+            None => self.limits,
+        };
+
+        let raw_body: &[u8] = &raw_bytes[head_length..];
+        let mut consumed: usize = head_length;
+
+        if request.is_chunked()? {
+            let (body, body_length): (Vec<u8>, usize) = self.read_chunked_body(raw_body, &limits).await?;
+            consumed += body_length;
+            request.set_body(body);
+        } else if let Some(content_length) = request.content_length()? {
+            limits.check_body_size(content_length)?;
+            consumed += content_length.min(raw_body.len());
+
+            let body: Cow<[u8]> = self.read_body(raw_body, content_length).await?;
+            request.set_body(body);
+        }
+
+        self.requests_served += 1;
+
+        let version: HttpVersion = request.version;
+        let remaining: usize = self.max_requests.saturating_sub(self.requests_served);
+        let keep_alive: bool = request.keep_alive() && remaining > 0;
+
         request.set_params(route.params);
-        let response: Response = (route.value)(request).await;
-        response.send(&mut self.stream).await?;
+        let mut response: Response = (route.value.handler)(request).await;
+        let keep_alive: bool = keep_alive && !shutdown::is_triggered(&self.shutdown);
+
+        if keep_alive && version == HttpVersion::Http10 {
+            let parameters: String = format!("timeout={}, max={remaining}", self.timeouts.keep_alive.as_secs());
+            response = response.header("Keep-Alive", parameters);
+        }
+
+        response.set_version(version);
+        response.set_keep_alive(keep_alive);
+        self.write_response(&response).await?;
+
+        if response.status() == HttpStatus::SwitchingProtocols
+            && let Some(on_upgrade) = response.take_upgrade()
+        {
+            debug!("Switching protocols after {} requests", self.requests_served);
+            self.upgrade = Some(on_upgrade);
+            return Ok((false, consumed));
+        }
+
+        debug!("Request finished successfully (keep-alive: {})", response.keep_alive());
+        Ok((response.keep_alive(), consumed))
+    }
+
+    async fn serve_upgrade(mut self, on_upgrade: OnUpgrade) {
+        let mut prefix: Vec<u8> = mem::take(&mut self.buffer);
+        prefix.append(&mut self.pending);
+
+        on_upgrade(Upgraded::new(Rewind::new(prefix, self.stream))).await;
+        debug!("Upgraded connection finished");
+    }
+
+    #[cfg(feature = "http2")]
+    async fn serve_http2(mut self) {
+        let mut prefix: Vec<u8> = mem::take(&mut self.buffer);
+        prefix.append(&mut self.pending);
+
+        let connection: Http2Connection<Rewind<S>> = Http2Connection {
+            router: self.router,
+            io: Rewind::new(prefix, self.stream),
+            limits: self.limits,
+            max_requests: self.max_requests,
+            timeouts: self.timeouts,
+            options: self.http2,
+            shutdown: self.shutdown,
+        };
+
+        connection.serve().await;
+    }
+
+    async fn read_head(&mut self, buffer: &mut Vec<u8>) -> Result<(), ListenerError> {
+        let limits: Limits = self.limits;
+        let mut searched: usize = 0;
+        let mut deadline: Option<Instant> = (!buffer.is_empty()).then(|| Instant::now() + self.timeouts.header_read);
+
+        loop {
+            if let Some(head_length) = Request::head_length(&buffer[searched..]) {
+                trace!("Request head of {} bytes received", searched + head_length);
+                return Ok(());
+            }
+
+            limits.check_head_size(buffer.len())?;
+            searched = buffer.len().saturating_sub(2);
+
+            buffer.reserve(BUFFER_SIZE);
+
+            let bytes_read: usize = match deadline {
+                Some(deadline) => timeout_at(deadline, self.stream.read_buf(buffer)).await.map_err(|_| {
+                    warn!("Header read timeout of {:?} elapsed", self.timeouts.header_read);
+                    HttpError::new(HttpStatus::RequestTimeout, "Timed out reading request headers")
+                })?,
+                None => {
+                    let wait: Duration = match self.requests_served {
+                        0 => self.timeouts.first_request,
+                        _ => self.timeouts.keep_alive,
+                    };
+
+                    if shutdown::is_triggered(&self.shutdown) {
+                        debug!("Closing idle connection for shutdown");
+                        return Err(ListenerError::ConnectionClosed);
+                    }
+
+                    tokio::select! {
+                        result = timeout(wait, self.stream.read_buf(buffer)) => result.map_err(|_| {
+                            debug!("Connection idle for {wait:?}, closing");
+                            ListenerError::ConnectionClosed
+                        })?,
+                        _ = shutdown::triggered(&mut self.shutdown) => {
+                            debug!("Closing idle connection for shutdown");
+                            return Err(ListenerError::ConnectionClosed);
+                        }
+                    }
+                }
+            }
+            .map_err(Self::map_read_error)?;
+
+            deadline.get_or_insert_with(|| Instant::now() + self.timeouts.header_read);
+
+            if bytes_read == 0 {
+                if !buffer.is_empty() {
+                    warn!("Connection closed after {} bytes of an incomplete request head", buffer.len());
+                }
+
+                return Err(ListenerError::ConnectionClosed);
+            }
+
+            trace!("Read {bytes_read} bytes into connection buffer ({} buffered)", buffer.len());
+        }
+    }
+
+    async fn discard_unread(&mut self) {
+        let mut unread: Take<&mut S> = (&mut self.stream).take(LINGER_LIMIT);
+
+        match timeout(LINGER_TIMEOUT, io::copy(&mut unread, &mut io::sink())).await {
+            Ok(Ok(bytes)) => trace!("Discarded {bytes} unread bytes before closing"),
+            Ok(Err(e)) => trace!("Failed to discard unread bytes: {e}"),
+            Err(_) => trace!("Timed out discarding unread bytes after {LINGER_TIMEOUT:?}"),
+        }
+    }
+
+    async fn write_response(&mut self, response: &Response<'_>) -> Result<(), ListenerError> {
+        timeout(self.timeouts.write, response.send(&mut self.stream))
+            .await
+            .map_err(|_| {
+                warn!("Response write timeout of {:?} elapsed", self.timeouts.write);
+                ListenerError::ConnectionClosed
+            })??;
 
-        debug!("Request finished successfully");
         Ok(())
     }
 
-    async fn read_request_bytes(&mut self, buffer: &mut [u8]) -> Result<usize, ListenerError> {
-        let bytes: usize = self.stream.read(buffer).await.map_err(|e: Error| match e.kind() {
-            ErrorKind::ConnectionReset | ErrorKind::BrokenPipe => ListenerError::ConnectionClosed,
-            _ => HttpError::new(HttpStatus::InternalServerError, "Failed to read data from stream").into(),
-        })?;
+    async fn read_request_bytes(&mut self, buffer: &mut [u8], deadline: Instant) -> Result<usize, ListenerError> {
+        let bytes: usize = timeout_at(deadline, self.stream.read(buffer))
+            .await
+            .map_err(|_| Self::body_timeout_error(&self.timeouts))?
+            .map_err(Self::map_read_error)?;
 
         if bytes == 0 {
             return Err(ListenerError::ConnectionClosed);
@@ -61,4 +355,69 @@ impl Connection {
 
         Ok(bytes)
     }
+
+    async fn read_body<'a>(
+        &mut self,
+        received: &'a [u8],
+        content_length: usize,
+    ) -> Result<Cow<'a, [u8]>, ListenerError> {
+        if let Some(body) = received.get(..content_length) {
+            trace!("Request body of {content_length} bytes received with the head");
+            return Ok(Cow::Borrowed(body));
+        }
+
+        let mut body: Vec<u8> = Vec::with_capacity(content_length.min(BUFFER_SIZE * 16));
+        body.extend_from_slice(received);
+
+        let remaining: u64 = u64::try_from(content_length - body.len())
+            .map_err(|_| HttpError::new(HttpStatus::PayloadTooLarge, "Request body is too large"))?;
+
+        let deadline: Instant = Instant::now() + self.timeouts.body_read;
+
+        timeout_at(deadline, (&mut self.stream).take(remaining).read_to_end(&mut body))
+            .await
+            .map_err(|_| Self::body_timeout_error(&self.timeouts))?
+            .map_err(Self::map_read_error)?;
+
+        if body.len() < content_length {
+            warn!("Connection closed after {} of {content_length} body bytes", body.len());
+            return Err(ListenerError::ConnectionClosed);
+        }
+
+        trace!("Read request body of {content_length} bytes");
+        Ok(Cow::Owned(body))
+    }
+
+    async fn read_chunked_body(&mut self, received: &[u8], limits: &Limits) -> Result<(Vec<u8>, usize), ListenerError> {
+        let mut decoder: ChunkedDecoder = ChunkedDecoder::new();
+        let mut body: Vec<u8> = Vec::new();
+        let consumed: usize = decoder.decode(received, &mut body)?;
+        limits.check_body_size(body.len())?;
+
+        let mut buffer: [u8; BUFFER_SIZE] = [0; BUFFER_SIZE];
+        let deadline: Instant = Instant::now() + self.timeouts.body_read;
+
+        while !decoder.is_done() {
+            let bytes_read: usize = self.read_request_bytes(&mut buffer, deadline).await?;
+            let used: usize = decoder.decode(&buffer[..bytes_read], &mut body)?;
+            limits.check_body_size(body.len())?;
+
+            self.pending.extend_from_slice(&buffer[used..bytes_read]);
+        }
+
+        trace!("Read chunked request body of {} bytes", body.len());
+        Ok((body, consumed))
+    }
+
+    fn body_timeout_error(timeouts: &Timeouts) -> ListenerError {
+        warn!("Body read timeout of {:?} elapsed", timeouts.body_read);
+        HttpError::new(HttpStatus::RequestTimeout, "Timed out reading request body").into()
+    }
+
+    fn map_read_error(e: Error) -> ListenerError {
+        match e.kind() {
+            ErrorKind::ConnectionReset | ErrorKind::BrokenPipe => ListenerError::ConnectionClosed,
+            _ => HttpError::new(HttpStatus::InternalServerError, "Failed to read data from stream").into(),
+        }
+    }
 }
@@ -2,10 +2,31 @@ use forge_http::HttpError;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ListenerError {
     #[error(transparent)]
     Http(#[from] HttpError),
 
     #[error("Connection closed by peer")]
     ConnectionClosed,
+
+    #[cfg(feature = "http2")]
+    #[error("Client sent the HTTP/2 connection preface")]
+    Http2Preface,
+}
+
+#[cfg(feature = "tls")]
+#[derive(Debug, Error)]
+pub enum TlsError {
+    #[error("Failed to read '{0}': {1}")]
+    Io(std::path::PathBuf, #[source] std::io::Error),
+
+    #[error("No certificates found in '{0}'")]
+    MissingCertificate(std::path::PathBuf),
+
+    #[error("No private key found in '{0}'")]
+    MissingPrivateKey(std::path::PathBuf),
+
+    #[error("Invalid TLS configuration: {0}")]
+    Rustls(#[from] rustls::Error),
 }
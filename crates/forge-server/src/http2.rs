@@ -0,0 +1,331 @@
+use std::borrow::Cow;
+use std::future::poll_fn;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use super::{ListenerError, ListenerOptions, Timeouts, shutdown};
+use bytes::Bytes;
+use forge_http::{
+    HeaderMap, HeaderName, HeaderValue, HttpError, HttpMethod, HttpStatus, HttpVersion, Limits, Request, Response,
+};
+use forge_router::{Endpoint, Router};
+use forge_utils::PathMatch;
+use h2::server::{self, Handshake, SendResponse};
+use h2::{Reason, RecvStream, SendStream};
+use http::Method;
+use http::request::Parts;
+use http::uri::{Authority, PathAndQuery};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::watch::Receiver;
+use tokio::task::JoinSet;
+use tokio::time::{Instant, sleep, timeout, timeout_at};
+use tracing::{debug, trace, warn};
+
+pub const ALPN_PROTOCOL: &[u8] = b"h2";
+pub(crate) const PREFACE_HEAD: &[u8] = b"PRI * HTTP/2.0\r\n\r\n";
+
+const HOST: &str = "host";
+const CONTENT_LENGTH: &str = "content-length";
+const CONNECTION_HEADERS: [&str; 5] = [
+    "connection",
+    "keep-alive",
+    "proxy-connection",
+    "transfer-encoding",
+    "upgrade",
+];
+
+#[derive(Debug, Clone, Copy)]
+pub struct Http2Options {
+    pub prior_knowledge: bool,
+    pub max_concurrent_streams: u32,
+}
+
+impl Default for Http2Options {
+    fn default() -> Self {
+        Self {
+            prior_knowledge: true,
+            max_concurrent_streams: 100,
+        }
+    }
+}
+
+pub struct Http2Connection<S> {
+    pub router: Arc<Router>,
+    pub io: S,
+    pub limits: Limits,
+    pub max_requests: usize,
+    pub timeouts: Timeouts,
+    pub options: Http2Options,
+    pub(crate) shutdown: Option<Receiver<bool>>,
+}
+
+impl<S> Http2Connection<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    pub fn new(router: Arc<Router>, io: S, options: &ListenerOptions) -> Self {
+        Self {
+            router,
+            io,
+            limits: options.limits,
+            max_requests: options.max_requests_per_connection,
+            timeouts: options.timeouts,
+            options: options.http2,
+            shutdown: None,
+        }
+    }
+
+    pub fn with_shutdown(mut self, shutdown: Receiver<bool>) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    pub async fn serve(self) {
+        let Self {
+            router,
+            io,
+            limits,
+            max_requests,
+            timeouts,
+            options,
+            mut shutdown,
+        } = self;
+
+        let max_header_list_size: u32 = u32::try_from(limits.max_head_size).unwrap_or(u32::MAX);
+        let handshake: Handshake<S, Bytes> = server::Builder::new()
+            .max_concurrent_streams(options.max_concurrent_streams)
+            .max_header_list_size(max_header_list_size)
+            .handshake::<S, Bytes>(io);
+
+        let mut connection: server::Connection<S, Bytes> = match timeout(timeouts.header_read, handshake).await {
+            Ok(Ok(connection)) => connection,
+            Ok(Err(e)) => {
+                debug!("HTTP/2 handshake failed: {e}");
+                return;
+            }
+            Err(_) => {
+                debug!("HTTP/2 handshake timed out after {:?}", timeouts.header_read);
+                return;
+            }
+        };
+
+        let mut streams: JoinSet<()> = JoinSet::new();
+        let mut requests_served: usize = 0;
+        let mut closing: bool = false;
+
+        loop {
+            tokio::select! {
+                accepted = connection.accept() => match accepted {
+                    Some(Ok((request, respond))) => {
+                        requests_served += 1;
+                        streams.spawn(Self::serve_stream(router.clone(), limits, timeouts, request, respond));
+
+                        if requests_served >= max_requests && !closing {
+                            debug!("HTTP/2 connection reached {max_requests} requests, closing");
+                            connection.graceful_shutdown();
+                            closing = true;
+                        }
+                    }
+                    Some(Err(e)) => {
+                        debug!("HTTP/2 connection error: {e}");
+                        break;
+                    }
+                    None => break,
+                },
+                Some(_) = streams.join_next(), if !streams.is_empty() => {}
+                _ = sleep(timeouts.keep_alive), if streams.is_empty() && !closing => {
+                    debug!("HTTP/2 connection idle for {:?}, closing", timeouts.keep_alive);
+                    connection.graceful_shutdown();
+                    closing = true;
+                }
+                _ = shutdown::triggered(&mut shutdown), if !closing => {
+                    debug!("Closing HTTP/2 connection for shutdown");
+                    connection.graceful_shutdown();
+                    closing = true;
+                }
+            }
+        }
+
+        debug!("Closing HTTP/2 connection after {requests_served} streams");
+    }
+
+    async fn serve_stream(
+        router: Arc<Router>,
+        limits: Limits,
+        timeouts: Timeouts,
+        request: http::Request<RecvStream>,
+        mut respond: SendResponse<Bytes>,
+    ) {
+        let (parts, body): (Parts, RecvStream) = request.into_parts();
+
+        match Self::handle_request(&router, &limits, &timeouts, &parts, body, &mut respond).await {
+            Ok(()) => trace!("HTTP/2 stream finished successfully"),
+            Err(ListenerError::Http(e)) => {
+                let response: Response = Response::new(e.status);
+
+                if let Err(e) = Self::write_response(&mut respond, &response, &parts, &timeouts).await {
+                    debug!("Failed to send HTTP/2 error response: {e}");
+                }
+            }
+            Err(_) => respond.send_reset(Reason::CANCEL),
+        }
+    }
+
+    async fn handle_request(
+        router: &Router,
+        limits: &Limits,
+        timeouts: &Timeouts,
+        parts: &Parts,
+        body: RecvStream,
+        respond: &mut SendResponse<Bytes>,
+    ) -> Result<(), ListenerError> {
+        let method: HttpMethod = HttpMethod::from_str(parts.method.as_str())?;
+        let target: &str = parts.uri.path_and_query().map(PathAndQuery::as_str).unwrap_or("/");
+
+        let mut headers: HeaderMap = HeaderMap::with_capacity(parts.headers.len() + 1);
+
+        if let Some(authority) = parts
+            .uri
+            .authority()
+            .filter(|_: &&Authority| !parts.headers.contains_key(HOST))
+        {
+            headers.append(HOST, authority.as_str());
+        }
+
+        for (name, value) in &parts.headers {
+            headers.append(name.as_str(), value.as_bytes());
+        }
+
+        let mut request: Request = Request::from_parts(method, target, HttpVersion::Http2, headers, limits)
+            .inspect_err(|e: &HttpError| {
+                warn!("Failed to build HTTP/2 request: {e}");
+            })?;
+
+        let path: Cow<str> = request.path.clone();
+        let route: PathMatch<Endpoint> = router.get_route(&path, &request.method).ok_or_else(|| {
+            warn!("404 Not Found: [{}] \"{}\"", request.method, request.path);
+            HttpError::new(HttpStatus::NotFound, "The requested resource could not be found")
+        })?;
+
+        let limits: Limits = match route.value.limits {
+            Some(route_limits) => {
+                let limits: Limits = route_limits.within(limits);
+                limits.check_request(&request, 0)?;
+                limits
+            }
+            None => *limits,
+        };
+
+        if let Some(content_length) = request.content_length()? {
+            limits.check_body_size(content_length)?;
+        }
+
+        let body: Vec<u8> = Self::read_body(body, &limits, timeouts).await?;
+        request.set_body(body);
+        request.set_params(route.params);
+
+        let mut response: Response = (route.value.handler)(request).await;
+        response.set_version(HttpVersion::Http2);
+
+        Self::write_response(respond, &response, parts, timeouts).await
+    }
+
+    async fn write_response(
+        respond: &mut SendResponse<Bytes>,
+        response: &Response<'_>,
+        parts: &Parts,
+        timeouts: &Timeouts,
+    ) -> Result<(), ListenerError> {
+        let head_only: bool = parts.method == Method::HEAD;
+
+        timeout(timeouts.write, Self::send_response(respond, response, head_only))
+            .await
+            .map_err(|_| {
+                warn!("Response write timeout of {:?} elapsed", timeouts.write);
+                ListenerError::ConnectionClosed
+            })?
+            .map_err(|e: h2::Error| {
+                debug!("Failed to send HTTP/2 response: {e}");
+                ListenerError::ConnectionClosed
+            })
+    }
+
+    async fn read_body(mut body: RecvStream, limits: &Limits, timeouts: &Timeouts) -> Result<Vec<u8>, ListenerError> {
+        let mut buffer: Vec<u8> = Vec::new();
+        let deadline: Instant = Instant::now() + timeouts.body_read;
+
+        while let Some(chunk) = timeout_at(deadline, body.data()).await.map_err(|_| {
+            warn!("Body read timeout of {:?} elapsed", timeouts.body_read);
+            HttpError::new(HttpStatus::RequestTimeout, "Timed out reading request body")
+        })? {
+            let chunk: Bytes = chunk.map_err(|e: h2::Error| {
+                debug!("Failed to read HTTP/2 request body: {e}");
+                ListenerError::ConnectionClosed
+            })?;
+
+            let _ = body.flow_control().release_capacity(chunk.len());
+            buffer.extend_from_slice(&chunk);
+            limits.check_body_size(buffer.len())?;
+        }
+
+        trace!("Read HTTP/2 request body of {} bytes", buffer.len());
+        Ok(buffer)
+    }
+
+    async fn send_response(
+        respond: &mut SendResponse<Bytes>,
+        response: &Response<'_>,
+        head_only: bool,
+    ) -> Result<(), h2::Error> {
+        let body: &[u8] = response.body_bytes();
+        let mut head: http::response::Builder = http::Response::builder().status(u16::from(response.status()));
+
+        let headers = response
+            .headers()
+            .iter()
+            .filter(|(name, _): &(&HeaderName, &HeaderValue)| {
+                let name: &str = name.as_str();
+                name != CONTENT_LENGTH
+                    && !CONNECTION_HEADERS
+                        .iter()
+                        .any(|header: &&str| header.eq_ignore_ascii_case(name))
+            });
+
+        for (name, value) in headers {
+            head = head.header(name.as_str(), value.as_bytes());
+        }
+
+        let head: http::Response<()> = head
+            .header(CONTENT_LENGTH, body.len())
+            .body(())
+            .map_err(|e: http::Error| {
+                warn!("Response cannot be encoded as HTTP/2: {e}");
+                h2::Error::from(Reason::INTERNAL_ERROR)
+            })?;
+
+        let end_of_stream: bool = head_only || body.is_empty();
+        let mut stream: SendStream<Bytes> = respond.send_response(head, end_of_stream)?;
+
+        if !end_of_stream {
+            Self::send_body(&mut stream, Bytes::copy_from_slice(body)).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn send_body(stream: &mut SendStream<Bytes>, mut data: Bytes) -> Result<(), h2::Error> {
+        while !data.is_empty() {
+            stream.reserve_capacity(data.len());
+
+            let capacity: usize = match poll_fn(|cx| stream.poll_capacity(cx)).await {
+                Some(capacity) => capacity?,
+                None => return Err(h2::Error::from(Reason::CANCEL)),
+            };
+
+            let chunk: Bytes = data.split_to(capacity.min(data.len()));
+            stream.send_data(chunk, data.is_empty())?;
+        }
+
+        Ok(())
+    }
+}
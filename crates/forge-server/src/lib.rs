@@ -1,7 +1,30 @@
+mod accept;
+#[cfg(unix)]
+mod activation;
 pub mod connection;
 pub mod error;
+#[cfg(feature = "http2")]
+pub mod http2;
 pub mod listener;
+pub mod shutdown;
+mod socket;
+pub mod stream;
+pub mod timeouts;
+#[cfg(feature = "tls")]
+pub mod tls;
+#[cfg(unix)]
+pub mod unix;
 
 pub use connection::Connection;
 pub use error::ListenerError;
-pub use listener::{Listener, ListenerOptions};
+#[cfg(feature = "tls")]
+pub use error::TlsError;
+#[cfg(feature = "http2")]
+pub use http2::{Http2Connection, Http2Options};
+pub use listener::{BoundListener, Listener, ListenerOptions, OverloadPolicy};
+pub use stream::{Rewind, Stream};
+pub use timeouts::Timeouts;
+#[cfg(feature = "tls")]
+pub use tls::{TlsCertificate, TlsOptions};
+#[cfg(unix)]
+pub use unix::UnixSocketOptions;
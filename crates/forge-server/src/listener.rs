@@ -1,30 +1,137 @@
 use std::io::Error;
-use std::net::{Ipv4Addr, SocketAddr};
+use std::mem;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener as StdTcpListener};
 use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
 
-use super::{Connection, ListenerError};
-use forge_http::Response;
+use super::accept::{AcceptLoop, MAX_PENDING_REJECTIONS};
+use super::socket::ListenSocket;
+use super::stream::StreamAcceptor;
+use super::{Timeouts, shutdown};
+use forge_http::{Limits, ParseOptions};
 use forge_logging::init_logger;
 use forge_router::Router;
-use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Semaphore;
+use tokio::sync::watch::{self, Receiver, Sender};
+use tokio::task::JoinSet;
 use tracing::{debug, error, info, warn};
 
+#[cfg(unix)]
+use super::{UnixSocketOptions, activation};
+#[cfg(unix)]
+use std::os::unix::net::UnixListener as StdUnixListener;
+
+#[cfg(feature = "http2")]
+use super::Http2Options;
+#[cfg(feature = "tls")]
+use super::{TlsError, TlsOptions};
+#[cfg(feature = "tls")]
+use std::io::ErrorKind;
+#[cfg(feature = "tls")]
+use tokio_rustls::TlsAcceptor;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum OverloadPolicy {
+    #[default]
+    Backpressure,
+    Reject,
+}
+
 pub struct ListenerOptions {
     pub port: u16,
-    pub host: Ipv4Addr,
+    pub host: IpAddr,
+    pub addresses: Vec<SocketAddr>,
+    pub ipv6_only: bool,
+    pub parse_options: ParseOptions,
+    pub limits: Limits,
+    pub max_requests_per_connection: usize,
+    pub timeouts: Timeouts,
+    pub shutdown_grace_period: Duration,
+    pub max_connections: usize,
+    pub overload_policy: OverloadPolicy,
+    pub acceptors: usize,
+    pub pin_acceptors: bool,
+    #[cfg(feature = "tls")]
+    pub tls: Option<TlsOptions>,
+    #[cfg(feature = "http2")]
+    pub http2: Http2Options,
+    #[cfg(unix)]
+    pub unix: Option<UnixSocketOptions>,
+}
+
+impl ListenerOptions {
+    pub fn socket_addresses(&self) -> Vec<SocketAddr> {
+        match self.addresses.is_empty() {
+            true => vec![SocketAddr::new(self.host, self.port)],
+            false => self.addresses.clone(),
+        }
+    }
+}
+
+impl Default for ListenerOptions {
+    fn default() -> Self {
+        Self {
+            port: 3000,
+            host: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            addresses: Vec::new(),
+            ipv6_only: false,
+            parse_options: ParseOptions::strict(),
+            limits: Limits::default(),
+            max_requests_per_connection: 1000,
+            timeouts: Timeouts::default(),
+            shutdown_grace_period: Duration::from_secs(30),
+            max_connections: 10_000,
+            overload_policy: OverloadPolicy::default(),
+            acceptors: 1,
+            pin_acceptors: false,
+            #[cfg(feature = "tls")]
+            tls: None,
+            #[cfg(feature = "http2")]
+            http2: Http2Options::default(),
+            #[cfg(unix)]
+            unix: None,
+        }
+    }
 }
 
 pub struct Listener {
     router: Arc<Router>,
-    options: ListenerOptions,
+    options: Arc<ListenerOptions>,
+    inherited: Vec<ListenSocket>,
 }
 
 impl Listener {
     pub fn new(router: Router, options: ListenerOptions) -> Self {
         Self {
-            options,
+            options: Arc::new(options),
             router: Arc::new(router),
+            inherited: Vec::new(),
+        }
+    }
+
+    pub fn with_std_listener(mut self, listener: StdTcpListener) -> Self {
+        self.inherited.push(ListenSocket::Tcp(listener));
+        self
+    }
+
+    #[cfg(unix)]
+    pub fn with_std_unix_listener(mut self, listener: StdUnixListener) -> Self {
+        self.inherited.push(ListenSocket::Unix(listener));
+        self
+    }
+
+    #[cfg(unix)]
+    pub fn with_socket_activation(mut self) -> Result<Self, Error> {
+        let sockets: Vec<ListenSocket> = activation::listen_fds()?;
+
+        match sockets.len() {
+            0 => debug!("No sockets passed via socket activation"),
+            count => info!("Received {count} sockets via socket activation"),
         }
+
+        self.inherited.extend(sockets);
+        Ok(self)
     }
 
     pub fn with_default_logger(self) -> Self {
@@ -36,45 +143,210 @@ impl Listener {
         self
     }
 
+    pub async fn bind(mut self) -> Result<BoundListener, Error> {
+        let acceptor: StreamAcceptor = self.stream_acceptor()?;
+        let inherited: bool = !self.inherited.is_empty();
+
+        let acceptors: Vec<Vec<ListenSocket>> = match inherited {
+            true => vec![self.adopt_sockets()?],
+            false => self.bind_sockets()?,
+        };
+
+        #[cfg(all(unix, feature = "tls"))]
+        if self.options.tls.is_some() && acceptors.iter().flatten().any(ListenSocket::is_unix) {
+            error!("TLS is not supported on Unix socket listeners");
+            return Err(Error::new(ErrorKind::InvalidInput, "TLS is not supported on Unix sockets"));
+        }
+
+        #[cfg(unix)]
+        let unix_socket: Option<UnixSocketOptions> = match inherited {
+            true => None,
+            false => self.options.unix.clone(),
+        };
+
+        Ok(BoundListener {
+            router: self.router,
+            options: self.options,
+            acceptor,
+            acceptors,
+            #[cfg(unix)]
+            unix_socket,
+        })
+    }
+
     pub async fn run(self) -> Result<(), Error> {
-        let address: SocketAddr = SocketAddr::from((self.options.host, self.options.port));
-        debug!("Binding TCP listener to {address}");
-
-        let listener: TcpListener = TcpListener::bind(address).await?;
-        info!("Listener running on http://{address}");
-
-        loop {
-            match listener.accept().await {
-                Ok((stream, _address)) => {
-                    let router: Arc<Router> = self.router.clone();
-
-                    if let Err(e) = stream.set_nodelay(true) {
-                        warn!("Failed to set 'TCP_NODELAY': {e}");
-                    }
-
-                    tokio::spawn(async move { Self::handle_connection(stream, router).await });
-                }
-                Err(e) => {
-                    error!("Failed to accept connection: {e}");
-                }
+        self.bind().await?.run().await
+    }
+
+    pub async fn run_until<F>(self, signal: F) -> Result<(), Error>
+    where
+        F: Future<Output = ()>,
+    {
+        self.bind().await?.run_until(signal).await
+    }
+
+    fn bind_sockets(&self) -> Result<Vec<Vec<ListenSocket>>, Error> {
+        let count: usize = self.options.acceptors.max(1);
+
+        #[cfg(unix)]
+        if let Some(unix) = &self.options.unix {
+            if count > 1 {
+                warn!("Multiple acceptors require TCP addresses, using a single acceptor for the Unix socket");
             }
+
+            debug!("Binding Unix listener to {:?}", unix.path);
+            let listener: StdUnixListener = unix.bind()?;
+            info!("Listener running on unix:{}", unix.path.display());
+            return Ok(vec![vec![ListenSocket::Unix(listener)]]);
         }
+
+        let reuse_port: bool = count > 1;
+        let addresses: Vec<SocketAddr> = self.options.socket_addresses();
+        let mut acceptors: Vec<Vec<ListenSocket>> = (0..count).map(|_| Vec::with_capacity(addresses.len())).collect();
+
+        for address in addresses {
+            debug!("Binding TCP listener to {address}");
+            let socket: ListenSocket = self.bind_tcp(address, reuse_port)?;
+            let bound: SocketAddr = socket.local_addr().unwrap_or(address);
+            info!("Listener running on {}://{bound}", self.scheme());
+            acceptors[0].push(socket);
+
+            for sockets in acceptors.iter_mut().skip(1) {
+                sockets.push(self.bind_tcp(bound, reuse_port)?);
+            }
+        }
+
+        if reuse_port {
+            info!("Accepting connections with {count} 'SO_REUSEPORT' acceptors");
+        }
+
+        Ok(acceptors)
     }
 
-    async fn handle_connection(stream: TcpStream, router: Arc<Router>) {
-        let mut handler: Connection = Connection { router, stream };
+    fn bind_tcp(&self, address: SocketAddr, reuse_port: bool) -> Result<ListenSocket, Error> {
+        ListenSocket::bind_tcp(address, self.options.ipv6_only, reuse_port).inspect_err(|e: &Error| {
+            error!("Failed to bind TCP listener to {address}: {e}");
+        })
+    }
 
-        loop {
-            if let Err(e) = handler.process_request().await {
-                match e {
-                    ListenerError::ConnectionClosed => break,
-                    ListenerError::Http(e) => {
-                        if (Response::new(e.status).send(&mut handler.stream).await).is_err() {
-                            break;
+    fn adopt_sockets(&mut self) -> Result<Vec<ListenSocket>, Error> {
+        let sockets: Vec<ListenSocket> = mem::take(&mut self.inherited);
+
+        if self.options.acceptors > 1 {
+            warn!("Multiple acceptors are not supported for inherited listeners, using a single acceptor");
+        }
+
+        for socket in &sockets {
+            match socket.local_addr() {
+                Some(address) => info!("Listener running on inherited {}://{address}", self.scheme()),
+                None => info!("Listener running on inherited Unix socket"),
+            }
+        }
+
+        Ok(sockets)
+    }
+
+    #[cfg(feature = "tls")]
+    fn stream_acceptor(&self) -> Result<StreamAcceptor, Error> {
+        let tls: Option<TlsAcceptor> = match &self.options.tls {
+            Some(options) => Some(options.acceptor().map_err(|e: TlsError| {
+                error!("Failed to configure TLS: {e}");
+                Error::new(ErrorKind::InvalidInput, e)
+            })?),
+            None => None,
+        };
+
+        Ok(StreamAcceptor::with_tls(tls))
+    }
+
+    #[cfg(not(feature = "tls"))]
+    fn stream_acceptor(&self) -> Result<StreamAcceptor, Error> {
+        Ok(StreamAcceptor::default())
+    }
+
+    fn scheme(&self) -> &'static str {
+        #[cfg(feature = "tls")]
+        if self.options.tls.is_some() {
+            return "https";
+        }
+
+        "http"
+    }
+}
+
+pub struct BoundListener {
+    router: Arc<Router>,
+    options: Arc<ListenerOptions>,
+    acceptor: StreamAcceptor,
+    acceptors: Vec<Vec<ListenSocket>>,
+    #[cfg(unix)]
+    unix_socket: Option<UnixSocketOptions>,
+}
+
+impl BoundListener {
+    pub fn local_addrs(&self) -> Vec<SocketAddr> {
+        self.acceptors
+            .first()
+            .map(|sockets: &Vec<ListenSocket>| sockets.iter().filter_map(ListenSocket::local_addr).collect())
+            .unwrap_or_default()
+    }
+
+    pub async fn run(self) -> Result<(), Error> {
+        self.run_until(shutdown::signal()).await
+    }
+
+    pub async fn run_until<F>(self, signal: F) -> Result<(), Error>
+    where
+        F: Future<Output = ()>,
+    {
+        let (shutdown_sender, shutdown_receiver): (Sender<bool>, Receiver<bool>) = watch::channel(false);
+        let semaphore: Arc<Semaphore> = Arc::new(Semaphore::new(self.options.max_connections));
+        let rejections: Arc<Semaphore> = Arc::new(Semaphore::new(MAX_PENDING_REJECTIONS));
+        let mut loops: JoinSet<()> = JoinSet::new();
+
+        for (index, sockets) in self.acceptors.into_iter().enumerate() {
+            let accept_loop: AcceptLoop = AcceptLoop {
+                index,
+                router: self.router.clone(),
+                options: self.options.clone(),
+                acceptor: self.acceptor.clone(),
+                semaphore: semaphore.clone(),
+                rejections: rejections.clone(),
+                shutdown: shutdown_receiver.clone(),
+                handoff: None,
+            };
+
+            let spawned: Result<(), Error> = match self.options.pin_acceptors {
+                true => accept_loop.spawn_pinned(sockets).map(|thread: JoinHandle<()>| {
+                    loops.spawn_blocking(move || {
+                        if thread.join().is_err() {
+                            error!("Acceptor thread {index} panicked");
                         }
-                    }
-                }
+                    });
+                }),
+                false => accept_loop.spawn(sockets, &mut loops),
+            };
+
+            if let Err(e) = spawned {
+                error!("Failed to start acceptor {index}: {e}");
+                let _ = shutdown_sender.send(true);
+                while loops.join_next().await.is_some() {}
+                return Err(e);
             }
         }
+
+        signal.await;
+        info!("Stopping {} acceptors", loops.len());
+
+        let _ = shutdown_sender.send(true);
+        while loops.join_next().await.is_some() {}
+
+        #[cfg(unix)]
+        if let Some(unix) = &self.unix_socket {
+            unix.cleanup();
+        }
+
+        info!("Listener shut down");
+        Ok(())
     }
 }
@@ -0,0 +1,51 @@
+use std::future;
+
+use tokio::sync::watch::Receiver;
+use tracing::{info, warn};
+
+pub async fn signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!("Failed to listen for SIGINT: {e}");
+            future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut stream) => {
+                stream.recv().await;
+            }
+            Err(e) => {
+                warn!("Failed to listen for SIGTERM: {e}");
+                future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => info!("Received SIGINT, starting graceful shutdown"),
+        _ = terminate => info!("Received SIGTERM, starting graceful shutdown"),
+    }
+}
+
+pub(crate) fn is_triggered(receiver: &Option<Receiver<bool>>) -> bool {
+    receiver
+        .as_ref()
+        .is_some_and(|receiver: &Receiver<bool>| *receiver.borrow())
+}
+
+pub(crate) async fn triggered(receiver: &mut Option<Receiver<bool>>) {
+    match receiver {
+        Some(receiver) => {
+            let _ = receiver.wait_for(|triggered: &bool| *triggered).await;
+        }
+        None => future::pending::<()>().await,
+    }
+}
@@ -0,0 +1,158 @@
+use std::io::{Error, ErrorKind};
+use std::net::{SocketAddr, TcpListener as StdTcpListener};
+use std::task::{Context, Poll};
+
+use super::Stream;
+use socket2::{Domain, Protocol, Socket, Type};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, warn};
+
+#[cfg(unix)]
+use socket2::SockAddr;
+#[cfg(unix)]
+use std::os::unix::net::UnixListener as StdUnixListener;
+#[cfg(unix)]
+use tokio::net::unix::SocketAddr as UnixSocketAddr;
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+
+const LISTEN_BACKLOG: i32 = 1024;
+
+pub(crate) enum ListenSocket {
+    Tcp(StdTcpListener),
+    #[cfg(unix)]
+    Unix(StdUnixListener),
+}
+
+impl ListenSocket {
+    pub(crate) fn bind_tcp(address: SocketAddr, ipv6_only: bool, reuse_port: bool) -> Result<Self, Error> {
+        let socket: Socket = Socket::new(Domain::for_address(address), Type::STREAM, Some(Protocol::TCP))?;
+
+        if address.is_ipv6() {
+            socket.set_only_v6(ipv6_only)?;
+        }
+
+        #[cfg(unix)]
+        socket.set_reuse_address(true)?;
+
+        #[cfg(unix)]
+        if reuse_port {
+            socket.set_reuse_port(true)?;
+        }
+
+        #[cfg(not(unix))]
+        if reuse_port {
+            warn!("'SO_REUSEPORT' is not supported on this platform");
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "SO_REUSEPORT is not supported on this platform",
+            ));
+        }
+
+        socket.set_nonblocking(true)?;
+        socket.bind(&address.into())?;
+        socket.listen(LISTEN_BACKLOG)?;
+
+        debug!("Bound TCP socket to {address} (IPv6 only: {ipv6_only}, reuse port: {reuse_port})");
+        Ok(ListenSocket::Tcp(socket.into()))
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn inspect(socket: &Socket) -> Result<bool, Error> {
+        if socket.r#type()? != Type::STREAM {
+            warn!("Inherited socket is not a stream socket");
+            return Err(Error::new(ErrorKind::InvalidInput, "Inherited socket is not a stream socket"));
+        }
+
+        #[cfg(any(
+            target_os = "android",
+            target_os = "freebsd",
+            target_os = "fuchsia",
+            target_os = "linux"
+        ))]
+        if !socket.is_listener()? {
+            warn!("Inherited socket is not listening");
+            return Err(Error::new(ErrorKind::InvalidInput, "Inherited socket is not listening"));
+        }
+
+        socket.set_cloexec(true)?;
+        let address: SockAddr = socket.local_addr()?;
+        Ok(address.is_unix())
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn adopt(socket: Socket, unix: bool) -> Self {
+        match unix {
+            true => ListenSocket::Unix(socket.into()),
+            false => ListenSocket::Tcp(socket.into()),
+        }
+    }
+
+    #[cfg(all(unix, feature = "tls"))]
+    pub(crate) fn is_unix(&self) -> bool {
+        matches!(self, ListenSocket::Unix(_))
+    }
+
+    pub(crate) fn local_addr(&self) -> Option<SocketAddr> {
+        match self {
+            ListenSocket::Tcp(listener) => listener.local_addr().ok(),
+            #[cfg(unix)]
+            ListenSocket::Unix(_) => None,
+        }
+    }
+
+    pub(crate) fn into_async(self) -> Result<BoundSocket, Error> {
+        match self {
+            ListenSocket::Tcp(listener) => {
+                listener.set_nonblocking(true)?;
+                Ok(BoundSocket::Tcp(TcpListener::from_std(listener)?))
+            }
+            #[cfg(unix)]
+            ListenSocket::Unix(listener) => {
+                listener.set_nonblocking(true)?;
+                Ok(BoundSocket::Unix(UnixListener::from_std(listener)?))
+            }
+        }
+    }
+}
+
+pub(crate) enum BoundSocket {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+impl BoundSocket {
+    pub(crate) fn poll_accept(&self, cx: &mut Context<'_>) -> Poll<Result<Stream, Error>> {
+        match self {
+            BoundSocket::Tcp(listener) => listener.poll_accept(cx).map_ok(|(stream, _): (TcpStream, SocketAddr)| {
+                if let Err(e) = stream.set_nodelay(true) {
+                    warn!("Failed to set 'TCP_NODELAY': {e}");
+                }
+
+                stream.into()
+            }),
+            #[cfg(unix)]
+            BoundSocket::Unix(listener) => listener
+                .poll_accept(cx)
+                .map_ok(|(stream, _): (UnixStream, UnixSocketAddr)| stream.into()),
+        }
+    }
+
+    pub(crate) fn poll_accept_any(
+        sockets: &[BoundSocket],
+        cursor: &mut usize,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Stream, Error>> {
+        for offset in 0..sockets.len() {
+            let index: usize = (*cursor + offset) % sockets.len();
+
+            if let Poll::Ready(result) = sockets[index].poll_accept(cx) {
+                *cursor = index + 1;
+                return Poll::Ready(result);
+            }
+        }
+
+        Poll::Pending
+    }
+}
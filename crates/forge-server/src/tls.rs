@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Error};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::TlsError;
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::{ClientHello, ResolvesServerCert};
+use rustls::sign::CertifiedKey;
+use rustls::{ServerConfig, crypto::ring};
+use serde::Deserialize;
+use tokio_rustls::TlsAcceptor;
+use tracing::{debug, trace, warn};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TlsCertificate {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+}
+
+impl TlsCertificate {
+    pub fn new(cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        Self {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+        }
+    }
+
+    fn load(&self, provider: &CryptoProvider) -> Result<CertifiedKey, TlsError> {
+        let certificates: Vec<CertificateDer<'static>> = rustls_pemfile::certs(&mut Self::open(&self.cert_path)?)
+            .collect::<Result<_, Error>>()
+            .map_err(|e: Error| TlsError::Io(self.cert_path.clone(), e))?;
+
+        if certificates.is_empty() {
+            warn!("No certificates found in {:?}", self.cert_path);
+            return Err(TlsError::MissingCertificate(self.cert_path.clone()));
+        }
+
+        let key: PrivateKeyDer<'static> = rustls_pemfile::private_key(&mut Self::open(&self.key_path)?)
+            .map_err(|e: Error| TlsError::Io(self.key_path.clone(), e))?
+            .ok_or_else(|| {
+                warn!("No private key found in {:?}", self.key_path);
+                TlsError::MissingPrivateKey(self.key_path.clone())
+            })?;
+
+        Ok(CertifiedKey::from_der(certificates, key, provider)?)
+    }
+
+    fn open(path: &Path) -> Result<BufReader<File>, TlsError> {
+        let file: File = File::open(path).map_err(|e: Error| {
+            warn!("Failed to open TLS file {path:?}: {e}");
+            TlsError::Io(path.to_path_buf(), e)
+        })?;
+
+        Ok(BufReader::new(file))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TlsOptions {
+    #[serde(flatten)]
+    pub certificate: TlsCertificate,
+    #[serde(default)]
+    pub sni: HashMap<String, TlsCertificate>,
+    #[serde(default = "TlsOptions::default_alpn_protocols")]
+    pub alpn_protocols: Vec<String>,
+}
+
+impl TlsOptions {
+    pub fn new(cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        Self {
+            certificate: TlsCertificate::new(cert_path, key_path),
+            sni: HashMap::new(),
+            alpn_protocols: Self::default_alpn_protocols(),
+        }
+    }
+
+    pub fn with_sni_certificate(
+        mut self,
+        server_name: impl Into<String>,
+        cert_path: impl Into<PathBuf>,
+        key_path: impl Into<PathBuf>,
+    ) -> Self {
+        let server_name: String = server_name.into().to_ascii_lowercase();
+        self.sni.insert(server_name, TlsCertificate::new(cert_path, key_path));
+        self
+    }
+
+    pub fn with_alpn_protocols<I, P>(mut self, protocols: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<String>,
+    {
+        self.alpn_protocols = protocols.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn server_config(&self) -> Result<ServerConfig, TlsError> {
+        let provider: Arc<CryptoProvider> = Arc::new(ring::default_provider());
+        let resolver: SniResolver = SniResolver::load(self, &provider)?;
+
+        let mut config: ServerConfig = ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .with_no_client_auth()
+            .with_cert_resolver(Arc::new(resolver));
+
+        config.alpn_protocols = self
+            .alpn_protocols
+            .iter()
+            .map(|protocol: &String| protocol.as_bytes().to_vec())
+            .collect();
+
+        debug!("TLS configured with ALPN protocols {:?}", self.alpn_protocols);
+        Ok(config)
+    }
+
+    pub fn acceptor(&self) -> Result<TlsAcceptor, TlsError> {
+        Ok(TlsAcceptor::from(Arc::new(self.server_config()?)))
+    }
+
+    #[cfg(feature = "http2")]
+    fn default_alpn_protocols() -> Vec<String> {
+        vec!["h2".to_owned(), "http/1.1".to_owned()]
+    }
+
+    #[cfg(not(feature = "http2"))]
+    fn default_alpn_protocols() -> Vec<String> {
+        vec!["http/1.1".to_owned()]
+    }
+}
+
+#[derive(Debug)]
+struct SniResolver {
+    default: Arc<CertifiedKey>,
+    by_name: HashMap<String, Arc<CertifiedKey>>,
+}
+
+impl SniResolver {
+    fn load(options: &TlsOptions, provider: &CryptoProvider) -> Result<Self, TlsError> {
+        let default: Arc<CertifiedKey> = Arc::new(options.certificate.load(provider)?);
+        let mut by_name: HashMap<String, Arc<CertifiedKey>> = HashMap::with_capacity(options.sni.len());
+
+        for (server_name, certificate) in &options.sni {
+            debug!("Loading TLS certificate for server name '{server_name}'");
+            by_name.insert(server_name.to_ascii_lowercase(), Arc::new(certificate.load(provider)?));
+        }
+
+        Ok(Self { default, by_name })
+    }
+}
+
+impl ResolvesServerCert for SniResolver {
+    fn resolve(&self, client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        let server_name: Option<String> = client_hello.server_name().map(str::to_ascii_lowercase);
+
+        match server_name.as_deref().and_then(|name: &str| self.by_name.get(name)) {
+            Some(key) => {
+                trace!("Selected TLS certificate for server name {server_name:?}");
+                Some(key.clone())
+            }
+            None => {
+                trace!("Using default TLS certificate for server name {server_name:?}");
+                Some(self.default.clone())
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use std::ffi::OsStr;
+use std::fs::{self, DirBuilder, Metadata, Permissions};
+use std::io::{Error, ErrorKind};
+use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use tracing::{debug, warn};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnixSocketOptions {
+    pub path: PathBuf,
+    pub mode: Option<u32>,
+    pub remove_stale: bool,
+}
+
+impl UnixSocketOptions {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            mode: None,
+            remove_stale: true,
+        }
+    }
+
+    pub fn with_mode(mut self, mode: u32) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    pub fn with_remove_stale(mut self, remove_stale: bool) -> Self {
+        self.remove_stale = remove_stale;
+        self
+    }
+
+    pub(crate) fn bind(&self) -> Result<UnixListener, Error> {
+        if self.remove_stale {
+            Self::remove_stale_socket(&self.path)?;
+        }
+
+        match self.mode {
+            Some(mode) => self.bind_with_mode(mode),
+            None => UnixListener::bind(&self.path),
+        }
+    }
+
+    fn bind_with_mode(&self, mode: u32) -> Result<UnixListener, Error> {
+        let file_name: &OsStr = self.path.file_name().ok_or_else(|| {
+            warn!("Unix socket path {:?} has no file name", self.path);
+            Error::new(ErrorKind::InvalidInput, format!("{:?} is not a socket path", self.path))
+        })?;
+
+        let parent: &Path = self
+            .path
+            .parent()
+            .filter(|parent: &&Path| !parent.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let staging: PathBuf = parent.join(format!(".{}.{}", file_name.to_string_lossy(), process::id()));
+
+        DirBuilder::new()
+            .mode(0o700)
+            .create(&staging)
+            .inspect_err(|e: &Error| {
+                warn!("Failed to create staging directory {staging:?}: {e}");
+            })?;
+
+        let result: Result<UnixListener, Error> = Self::bind_staged(&staging.join("sock"), &self.path, mode);
+
+        if let Err(e) = fs::remove_dir_all(&staging) {
+            warn!("Failed to remove staging directory {staging:?}: {e}");
+        }
+
+        result
+    }
+
+    fn bind_staged(staged: &Path, path: &Path, mode: u32) -> Result<UnixListener, Error> {
+        let listener: UnixListener = UnixListener::bind(staged)?;
+
+        fs::set_permissions(staged, Permissions::from_mode(mode)).inspect_err(|e: &Error| {
+            warn!("Failed to set permissions {mode:o} on {path:?}: {e}");
+        })?;
+
+        fs::hard_link(staged, path).map_err(|e: Error| match e.kind() {
+            ErrorKind::AlreadyExists => {
+                warn!("Refusing to replace existing file at {path:?}");
+                Error::new(ErrorKind::AddrInUse, format!("{path:?} is already in use"))
+            }
+            _ => e,
+        })?;
+
+        Ok(listener)
+    }
+
+    pub(crate) fn cleanup(&self) {
+        match fs::remove_file(&self.path) {
+            Ok(()) => debug!("Removed Unix socket {:?}", self.path),
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => warn!("Failed to remove Unix socket {:?}: {e}", self.path),
+        }
+    }
+
+    fn remove_stale_socket(path: &Path) -> Result<(), Error> {
+        let metadata: Metadata = match fs::symlink_metadata(path) {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        if !metadata.file_type().is_socket() {
+            warn!("Refusing to replace {path:?}, which is not a Unix socket");
+            return Err(Error::new(ErrorKind::AlreadyExists, format!("{path:?} is not a Unix socket")));
+        }
+
+        match UnixStream::connect(path) {
+            Ok(_) => {
+                warn!("Unix socket {path:?} is still accepting connections");
+                Err(Error::new(ErrorKind::AddrInUse, format!("{path:?} is already in use")))
+            }
+            Err(e) if e.kind() == ErrorKind::ConnectionRefused => {
+                debug!("Removing stale Unix socket {path:?}");
+                fs::remove_file(path)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
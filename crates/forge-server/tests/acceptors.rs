@@ -0,0 +1,147 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::io::Error;
+use std::net::{Ipv4Addr, SocketAddr};
+
+use common::{read_all, send_and_read_all, spawn_bound_listener};
+use forge_server::ListenerOptions;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, sleep, timeout};
+
+const REQUEST: &[u8] = b"GET /a HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+
+fn acceptor_options(acceptors: usize, pin_acceptors: bool) -> ListenerOptions {
+    ListenerOptions {
+        port: 0,
+        acceptors,
+        pin_acceptors,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_reuse_port_acceptors_share_address() {
+    let (addresses, trigger, handle): (Vec<SocketAddr>, oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_bound_listener(acceptor_options(4, false)).await;
+
+    assert_eq!(addresses.len(), 1);
+    assert_ne!(addresses[0].port(), 0);
+
+    for _ in 0..32 {
+        assert!(
+            send_and_read_all(addresses[0], REQUEST)
+                .await
+                .starts_with("HTTP/1.1 200 OK\r\n")
+        );
+    }
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn test_idle_acceptors_do_not_hold_connection_slots() {
+    let options: ListenerOptions = ListenerOptions {
+        max_connections: 2,
+        ..acceptor_options(4, false)
+    };
+
+    let (addresses, trigger, handle): (Vec<SocketAddr>, oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_bound_listener(options).await;
+
+    for _ in 0..32 {
+        let response: String = timeout(Duration::from_secs(2), send_and_read_all(addresses[0], REQUEST))
+            .await
+            .expect("Connection should not wait behind idle acceptors");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    }
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn test_reuse_port_acceptors_per_address() {
+    let ephemeral: SocketAddr = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+    let options: ListenerOptions = ListenerOptions {
+        addresses: vec![ephemeral, ephemeral],
+        acceptors: 2,
+        ..Default::default()
+    };
+
+    let (addresses, trigger, handle): (Vec<SocketAddr>, oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_bound_listener(options).await;
+
+    assert_eq!(addresses.len(), 2);
+
+    for address in addresses {
+        assert!(
+            send_and_read_all(address, REQUEST)
+                .await
+                .starts_with("HTTP/1.1 200 OK\r\n")
+        );
+    }
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn test_pinned_acceptors_serve_requests() {
+    let (addresses, trigger, handle): (Vec<SocketAddr>, oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_bound_listener(acceptor_options(2, true)).await;
+
+    for _ in 0..16 {
+        assert!(
+            send_and_read_all(addresses[0], REQUEST)
+                .await
+                .starts_with("HTTP/1.1 200 OK\r\n")
+        );
+    }
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn test_pinned_acceptors_hand_connections_to_runtime() {
+    let (addresses, trigger, handle): (Vec<SocketAddr>, oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_bound_listener(acceptor_options(1, true)).await;
+
+    let raw: &[u8] = b"GET /thread HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+    let response: String = send_and_read_all(addresses[0], raw).await;
+
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(!response.contains("forge-acceptor"), "{response}");
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn test_pinned_acceptors_drain_on_shutdown() {
+    let (addresses, trigger, handle): (Vec<SocketAddr>, oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_bound_listener(acceptor_options(2, true)).await;
+
+    let mut stream: TcpStream = TcpStream::connect(addresses[0]).await.unwrap();
+    stream
+        .write_all(b"GET /slow HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+
+    sleep(Duration::from_millis(50)).await;
+    trigger.send(()).unwrap();
+
+    let response: String = read_all(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("Connection: close\r\n"));
+    assert!(response.ends_with("slow"));
+
+    handle.await.unwrap().unwrap();
+    assert!(TcpStream::connect(addresses[0]).await.is_err());
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use std::io::Error;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use common::{echo_router, send_and_read_all, spawn_bound_listener};
+use forge_server::{BoundListener, Listener, ListenerOptions};
+use tokio::net::TcpStream;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+const REQUEST: &[u8] = b"GET /a HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+
+fn address_options(addresses: Vec<SocketAddr>, ipv6_only: bool) -> ListenerOptions {
+    ListenerOptions {
+        addresses,
+        ipv6_only,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_port_zero_reports_bound_address() {
+    let options: ListenerOptions = ListenerOptions {
+        port: 0,
+        ..Default::default()
+    };
+
+    let (addresses, trigger, handle): (Vec<SocketAddr>, oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_bound_listener(options).await;
+
+    assert_eq!(addresses.len(), 1);
+    assert_eq!(addresses[0].ip(), IpAddr::V4(Ipv4Addr::LOCALHOST));
+    assert_ne!(addresses[0].port(), 0);
+    assert!(
+        send_and_read_all(addresses[0], REQUEST)
+            .await
+            .starts_with("HTTP/1.1 200 OK\r\n")
+    );
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn test_serves_multiple_addresses() {
+    let ephemeral: SocketAddr = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+    let (addresses, trigger, handle): (Vec<SocketAddr>, oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_bound_listener(address_options(vec![ephemeral, ephemeral], false)).await;
+
+    assert_eq!(addresses.len(), 2);
+    assert_ne!(addresses[0], addresses[1]);
+
+    for address in addresses {
+        let response: String = send_and_read_all(address, REQUEST).await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    }
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn test_serves_ipv6_loopback() {
+    let (addresses, trigger, handle): (Vec<SocketAddr>, oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_bound_listener(address_options(vec![SocketAddr::from((Ipv6Addr::LOCALHOST, 0))], true)).await;
+
+    assert!(addresses[0].is_ipv6());
+    assert!(
+        send_and_read_all(addresses[0], REQUEST)
+            .await
+            .starts_with("HTTP/1.1 200 OK\r\n")
+    );
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn test_dual_stack_accepts_ipv4() {
+    let (addresses, trigger, handle): (Vec<SocketAddr>, oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_bound_listener(address_options(vec![SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0))], false)).await;
+
+    let ipv4: SocketAddr = SocketAddr::from((Ipv4Addr::LOCALHOST, addresses[0].port()));
+    assert!(
+        send_and_read_all(ipv4, REQUEST)
+            .await
+            .starts_with("HTTP/1.1 200 OK\r\n")
+    );
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn test_ipv6_only_refuses_ipv4() {
+    let (addresses, trigger, handle): (Vec<SocketAddr>, oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_bound_listener(address_options(vec![SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0))], true)).await;
+
+    let ipv4: SocketAddr = SocketAddr::from((Ipv4Addr::LOCALHOST, addresses[0].port()));
+    assert!(TcpStream::connect(ipv4).await.is_err());
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn test_bind_fails_when_address_in_use() {
+    let (addresses, trigger, handle): (Vec<SocketAddr>, oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_bound_listener(address_options(vec![SocketAddr::from((Ipv4Addr::LOCALHOST, 0))], false)).await;
+
+    let ephemeral: SocketAddr = SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+    let listener: Listener = Listener::new(echo_router(), address_options(vec![ephemeral, addresses[0]], false));
+    let result: Result<BoundListener, Error> = listener.bind().await;
+    assert!(result.is_err());
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
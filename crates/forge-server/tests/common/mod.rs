@@ -0,0 +1,180 @@
+#![allow(dead_code)]
+
+use std::fs;
+use std::io::Error;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use forge_http::{HttpStatus, Limits, Message, Request, Response, WebSocket, WebSocketConfig};
+use forge_router::{Router, get, post, websocket};
+use forge_server::{BoundListener, Connection, Listener, ListenerOptions};
+use rcgen::CertifiedKey;
+use rustls::pki_types::CertificateDer;
+use tempfile::TempDir;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+use tokio::sync::watch::Receiver;
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, sleep};
+
+pub fn echo_handler(req: Request) -> Response {
+    let body: String = format!("{} {} {}", req.method, req.path, String::from_utf8_lossy(&req.body));
+    Response::new(HttpStatus::Ok).text(body)
+}
+
+pub fn thread_handler(_: Request) -> Response {
+    let name: String = std::thread::current().name().unwrap_or_default().to_owned();
+    Response::new(HttpStatus::Ok).text(name)
+}
+
+pub async fn slow_handler(_: Request<'_>) -> Response<'_> {
+    sleep(Duration::from_millis(300)).await;
+    Response::new(HttpStatus::Ok).text("slow")
+}
+
+pub async fn echo_socket(mut socket: WebSocket) {
+    while let Some(Ok(message)) = socket.recv().await {
+        if matches!(message, Message::Text(_) | Message::Binary(_)) && socket.send(message).await.is_err() {
+            break;
+        }
+    }
+}
+
+pub fn echo_router() -> Router {
+    let mut router: Router = Router::new();
+    get!(router, "/slow", slow_handler);
+    get!(router, "/a", echo_handler);
+    get!(router, "/b", echo_handler);
+    get!(router, "/thread", thread_handler);
+    post!(router, "/echo", echo_handler);
+    post!(
+        router,
+        "/upload",
+        echo_handler,
+        Limits {
+            max_uri_length: 64 * 1024,
+            max_body_size: 64 * 1024,
+            ..Limits::default()
+        }
+    );
+    websocket!(router, "/ws", echo_socket);
+    websocket!(
+        router,
+        "/ws/small",
+        echo_socket,
+        WebSocketConfig {
+            max_frame_size: 16,
+            max_message_size: 32,
+        }
+    );
+    router
+}
+
+pub async fn spawn_server(options: ListenerOptions) -> SocketAddr {
+    spawn_server_with_shutdown(options, None).await
+}
+
+pub async fn spawn_server_with_shutdown(options: ListenerOptions, shutdown: Option<Receiver<bool>>) -> SocketAddr {
+    let router: Arc<Router> = Arc::new(echo_router());
+    let options: Arc<ListenerOptions> = Arc::new(options);
+    let listener: TcpListener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address: SocketAddr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        loop {
+            let (stream, _): (TcpStream, SocketAddr) = listener.accept().await.unwrap();
+            let router: Arc<Router> = router.clone();
+            let options: Arc<ListenerOptions> = options.clone();
+            let connection: Connection<TcpStream> = Connection::new(router, stream, &options);
+
+            let connection: Connection<TcpStream> = match shutdown.clone() {
+                Some(shutdown) => connection.with_shutdown(shutdown),
+                None => connection,
+            };
+
+            tokio::spawn(connection.serve());
+        }
+    });
+
+    address
+}
+
+pub async fn read_all(stream: &mut TcpStream) -> String {
+    let mut response: Vec<u8> = Vec::new();
+    stream.read_to_end(&mut response).await.unwrap();
+    String::from_utf8(response).unwrap()
+}
+
+pub async fn send_and_read_all(address: SocketAddr, raw: &[u8]) -> String {
+    let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+    stream.write_all(raw).await.unwrap();
+    read_all(&mut stream).await
+}
+
+pub async fn free_port() -> u16 {
+    let listener: TcpListener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    listener.local_addr().unwrap().port()
+}
+
+pub async fn connect(port: u16) -> TcpStream {
+    for _ in 0..50 {
+        if let Ok(stream) = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).await {
+            return stream;
+        }
+
+        sleep(Duration::from_millis(10)).await;
+    }
+
+    panic!("Listener did not start on port {port}");
+}
+
+pub fn spawn_listener(options: ListenerOptions) -> (oneshot::Sender<()>, JoinHandle<Result<(), Error>>) {
+    let (trigger, signal): (oneshot::Sender<()>, oneshot::Receiver<()>) = oneshot::channel();
+    let listener: Listener = Listener::new(echo_router(), options);
+
+    let handle: JoinHandle<Result<(), Error>> = tokio::spawn(listener.run_until(async move {
+        let _ = signal.await;
+    }));
+
+    (trigger, handle)
+}
+
+pub async fn spawn_bound_listener(
+    options: ListenerOptions,
+) -> (Vec<SocketAddr>, oneshot::Sender<()>, JoinHandle<Result<(), Error>>) {
+    let (trigger, signal): (oneshot::Sender<()>, oneshot::Receiver<()>) = oneshot::channel();
+    let listener: BoundListener = Listener::new(echo_router(), options).bind().await.unwrap();
+    let addresses: Vec<SocketAddr> = listener.local_addrs();
+
+    let handle: JoinHandle<Result<(), Error>> = tokio::spawn(listener.run_until(async move {
+        let _ = signal.await;
+    }));
+
+    (addresses, trigger, handle)
+}
+
+pub struct TestCertificate {
+    pub directory: TempDir,
+    pub der: CertificateDer<'static>,
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+}
+
+pub fn generate_certificate(name: &str) -> TestCertificate {
+    let CertifiedKey { cert, key_pair } = rcgen::generate_simple_self_signed(vec![name.to_owned()]).unwrap();
+    let directory: TempDir = TempDir::new().unwrap();
+
+    let cert_path: PathBuf = directory.path().join(format!("{name}.crt"));
+    let key_path: PathBuf = directory.path().join(format!("{name}.key"));
+    fs::write(&cert_path, cert.pem()).unwrap();
+    fs::write(&key_path, key_pair.serialize_pem()).unwrap();
+
+    TestCertificate {
+        directory,
+        der: cert.der().clone(),
+        cert_path,
+        key_path,
+    }
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use std::io::Error;
+use std::net::{IpAddr, Ipv4Addr};
+use std::time::Duration;
+
+use common::{connect, free_port, read_all, spawn_listener};
+use forge_server::{ListenerOptions, OverloadPolicy};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time::error::Elapsed;
+use tokio::time::timeout;
+
+fn limited_options(port: u16, overload_policy: OverloadPolicy) -> ListenerOptions {
+    ListenerOptions {
+        port,
+        host: IpAddr::V4(Ipv4Addr::LOCALHOST),
+        max_connections: 1,
+        overload_policy,
+        ..Default::default()
+    }
+}
+
+async fn request(stream: &mut TcpStream, raw: &[u8]) -> String {
+    stream.write_all(raw).await.unwrap();
+
+    let mut buffer: [u8; 1024] = [0; 1024];
+    let length: usize = stream.read(&mut buffer).await.unwrap();
+    String::from_utf8_lossy(&buffer[..length]).into_owned()
+}
+
+#[tokio::test]
+async fn test_excess_connections_are_rejected_with_503() {
+    let port: u16 = free_port().await;
+    let (_trigger, _handle): (oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_listener(limited_options(port, OverloadPolicy::Reject));
+
+    let mut first: TcpStream = connect(port).await;
+    let response: String = request(&mut first, b"GET /a HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+
+    let mut second: TcpStream = connect(port).await;
+    second
+        .write_all(b"GET /a HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+
+    let response: String = read_all(&mut second).await;
+    assert!(response.starts_with("HTTP/1.1 503 Service Unavailable\r\nConnection: close\r\n"));
+}
+
+#[tokio::test]
+async fn test_backpressure_defers_excess_connections() {
+    let port: u16 = free_port().await;
+    let (_trigger, _handle): (oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_listener(limited_options(port, OverloadPolicy::Backpressure));
+
+    let mut first: TcpStream = connect(port).await;
+    let response: String = request(&mut first, b"GET /a HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+
+    let mut second: TcpStream = connect(port).await;
+    second
+        .write_all(b"GET /b HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+
+    let mut buffer: [u8; 64] = [0; 64];
+    let pending: Result<Result<usize, Error>, Elapsed> =
+        timeout(Duration::from_millis(200), second.read(&mut buffer)).await;
+    assert!(pending.is_err(), "Second connection should wait for a free slot");
+
+    drop(first);
+
+    let response: String = timeout(Duration::from_secs(2), read_all(&mut second)).await.unwrap();
+    assert!(response.ends_with("GET /b "));
+}
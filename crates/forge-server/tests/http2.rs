@@ -0,0 +1,129 @@
+#![cfg(feature = "http2")]
+
+mod common;
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use bytes::Bytes;
+use common::spawn_server;
+use forge_server::ListenerOptions;
+use h2::client::{self, ResponseFuture, SendRequest};
+use h2::{RecvStream, SendStream};
+use http::{Method, StatusCode};
+use tokio::net::TcpStream;
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, timeout};
+
+async fn connect_h2c(address: SocketAddr) -> SendRequest<Bytes> {
+    let stream: TcpStream = TcpStream::connect(address).await.unwrap();
+    let (client, connection): (SendRequest<Bytes>, client::Connection<TcpStream, Bytes>) =
+        client::handshake(stream).await.unwrap();
+
+    tokio::spawn(connection);
+    client
+}
+
+async fn send(client: &SendRequest<Bytes>, method: Method, path: &str, body: &'static [u8]) -> (StatusCode, String) {
+    let request: http::Request<()> = http::Request::builder()
+        .method(method)
+        .uri(format!("http://localhost{path}"))
+        .body(())
+        .unwrap();
+
+    let mut client: SendRequest<Bytes> = client.clone().ready().await.unwrap();
+    let (response, mut stream): (ResponseFuture, SendStream<Bytes>) =
+        client.send_request(request, body.is_empty()).unwrap();
+
+    if !body.is_empty() {
+        stream.send_data(Bytes::from_static(body), true).unwrap();
+    }
+
+    let response: http::Response<RecvStream> = response.await.unwrap();
+    let status: StatusCode = response.status();
+    let mut body: RecvStream = response.into_body();
+    let mut bytes: Vec<u8> = Vec::new();
+
+    while let Some(chunk) = body.data().await {
+        let chunk: Bytes = chunk.unwrap();
+        let _ = body.flow_control().release_capacity(chunk.len());
+        bytes.extend_from_slice(&chunk);
+    }
+
+    (status, String::from_utf8(bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_h2c_prior_knowledge_request() {
+    let address: SocketAddr = spawn_server(ListenerOptions::default()).await;
+    let client: SendRequest<Bytes> = connect_h2c(address).await;
+
+    let (status, body): (StatusCode, String) = send(&client, Method::GET, "/a?x=1", b"").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "GET /a ");
+}
+
+#[tokio::test]
+async fn test_h2c_request_with_body() {
+    let address: SocketAddr = spawn_server(ListenerOptions::default()).await;
+    let client: SendRequest<Bytes> = connect_h2c(address).await;
+
+    let (status, body): (StatusCode, String) = send(&client, Method::POST, "/echo", b"hello h2").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "POST /echo hello h2");
+}
+
+#[tokio::test]
+async fn test_h2c_streams_are_multiplexed() {
+    let address: SocketAddr = spawn_server(ListenerOptions::default()).await;
+    let client: SendRequest<Bytes> = connect_h2c(address).await;
+    let started: Instant = Instant::now();
+
+    let (slow, fast, other): ((StatusCode, String), (StatusCode, String), (StatusCode, String)) = tokio::join!(
+        send(&client, Method::GET, "/slow", b""),
+        send(&client, Method::GET, "/a", b""),
+        send(&client, Method::GET, "/slow", b""),
+    );
+
+    assert_eq!(slow.1, "slow");
+    assert_eq!(fast.1, "GET /a ");
+    assert_eq!(other.1, "slow");
+    assert!(started.elapsed() < Duration::from_millis(550));
+}
+
+#[tokio::test]
+async fn test_h2c_errors_are_sent_per_stream() {
+    let address: SocketAddr = spawn_server(ListenerOptions::default()).await;
+    let client: SendRequest<Bytes> = connect_h2c(address).await;
+
+    let (missing, _): (StatusCode, String) = send(&client, Method::GET, "/missing", b"").await;
+    let (found, body): (StatusCode, String) = send(&client, Method::GET, "/b", b"").await;
+
+    assert_eq!(missing, StatusCode::NOT_FOUND);
+    assert_eq!(found, StatusCode::OK);
+    assert_eq!(body, "GET /b ");
+}
+
+#[tokio::test]
+async fn test_h2c_max_requests_per_connection_sends_goaway() {
+    let address: SocketAddr = spawn_server(ListenerOptions {
+        max_requests_per_connection: 2,
+        ..Default::default()
+    })
+    .await;
+
+    let stream: TcpStream = TcpStream::connect(address).await.unwrap();
+    let (client, connection): (SendRequest<Bytes>, client::Connection<TcpStream, Bytes>) =
+        client::handshake(stream).await.unwrap();
+    let connection: JoinHandle<Result<(), h2::Error>> = tokio::spawn(connection);
+
+    for path in ["/a", "/b"] {
+        let (status, _): (StatusCode, String) = send(&client, Method::GET, path, b"").await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let closed: Result<(), h2::Error> = timeout(Duration::from_secs(2), connection).await.unwrap().unwrap();
+    assert!(closed.is_ok());
+}
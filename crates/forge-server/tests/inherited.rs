@@ -0,0 +1,135 @@
+mod common;
+
+use std::io::Error;
+use std::net::{SocketAddr, TcpListener as StdTcpListener};
+
+use common::{echo_router, send_and_read_all};
+use forge_server::{BoundListener, Listener, ListenerOptions};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+const REQUEST: &[u8] = b"GET /a HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+
+fn run(listener: BoundListener) -> (oneshot::Sender<()>, JoinHandle<Result<(), Error>>) {
+    let (trigger, signal): (oneshot::Sender<()>, oneshot::Receiver<()>) = oneshot::channel();
+
+    let handle: JoinHandle<Result<(), Error>> = tokio::spawn(listener.run_until(async move {
+        let _ = signal.await;
+    }));
+
+    (trigger, handle)
+}
+
+#[tokio::test]
+async fn test_serves_std_tcp_listener() {
+    let std_listener: StdTcpListener = StdTcpListener::bind("127.0.0.1:0").unwrap();
+    let address: SocketAddr = std_listener.local_addr().unwrap();
+
+    let listener: BoundListener = Listener::new(echo_router(), ListenerOptions::default())
+        .with_std_listener(std_listener)
+        .bind()
+        .await
+        .unwrap();
+
+    assert_eq!(listener.local_addrs(), vec![address]);
+
+    let (trigger, handle): (oneshot::Sender<()>, JoinHandle<Result<(), Error>>) = run(listener);
+    assert!(
+        send_and_read_all(address, REQUEST)
+            .await
+            .starts_with("HTTP/1.1 200 OK\r\n")
+    );
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn test_serves_multiple_std_tcp_listeners() {
+    let first: StdTcpListener = StdTcpListener::bind("127.0.0.1:0").unwrap();
+    let second: StdTcpListener = StdTcpListener::bind("127.0.0.1:0").unwrap();
+
+    let listener: BoundListener = Listener::new(echo_router(), ListenerOptions::default())
+        .with_std_listener(first)
+        .with_std_listener(second)
+        .bind()
+        .await
+        .unwrap();
+
+    let addresses: Vec<SocketAddr> = listener.local_addrs();
+    let (trigger, handle): (oneshot::Sender<()>, JoinHandle<Result<(), Error>>) = run(listener);
+
+    for address in addresses {
+        assert!(
+            send_and_read_all(address, REQUEST)
+                .await
+                .starts_with("HTTP/1.1 200 OK\r\n")
+        );
+    }
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_serves_std_unix_listener_without_removing_it() {
+    use std::os::unix::net::UnixListener as StdUnixListener;
+    use std::path::PathBuf;
+
+    use tempfile::TempDir;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::UnixStream;
+
+    let directory: TempDir = TempDir::new().unwrap();
+    let path: PathBuf = directory.path().join("inherited.sock");
+
+    let listener: BoundListener = Listener::new(echo_router(), ListenerOptions::default())
+        .with_std_unix_listener(StdUnixListener::bind(&path).unwrap())
+        .bind()
+        .await
+        .unwrap();
+
+    assert!(listener.local_addrs().is_empty());
+    let (trigger, handle): (oneshot::Sender<()>, JoinHandle<Result<(), Error>>) = run(listener);
+
+    let mut stream: UnixStream = UnixStream::connect(&path).await.unwrap();
+    stream.write_all(REQUEST).await.unwrap();
+
+    let mut response: Vec<u8> = Vec::new();
+    stream.read_to_end(&mut response).await.unwrap();
+    assert!(response.starts_with(b"HTTP/1.1 200 OK\r\n"));
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+    assert!(path.exists());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_socket_activation_falls_back_to_binding() {
+    let options: ListenerOptions = ListenerOptions {
+        port: 0,
+        ..Default::default()
+    };
+
+    let listener: BoundListener = Listener::new(echo_router(), options)
+        .with_socket_activation()
+        .unwrap()
+        .bind()
+        .await
+        .unwrap();
+
+    let addresses: Vec<SocketAddr> = listener.local_addrs();
+    assert_eq!(addresses.len(), 1);
+
+    let (trigger, handle): (oneshot::Sender<()>, JoinHandle<Result<(), Error>>) = run(listener);
+    assert!(
+        send_and_read_all(addresses[0], REQUEST)
+            .await
+            .starts_with("HTTP/1.1 200 OK\r\n")
+    );
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use common::{read_all, send_and_read_all, spawn_server};
+use forge_server::{ListenerOptions, Timeouts};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::{Instant, sleep};
+
+async fn read_response(stream: &mut TcpStream) -> String {
+    let mut response: Vec<u8> = Vec::new();
+
+    loop {
+        let text: String = String::from_utf8_lossy(&response).into_owned();
+
+        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+            let content_length: usize = head
+                .lines()
+                .find_map(|line: &str| line.strip_prefix("Content-Length: "))
+                .map(|length: &str| length.parse::<usize>().unwrap())
+                .unwrap_or(0);
+
+            if body.len() >= content_length {
+                return text;
+            }
+        }
+
+        let mut chunk: [u8; 1024] = [0; 1024];
+        let bytes_read: usize = stream.read(&mut chunk).await.unwrap();
+        assert!(bytes_read > 0, "Connection closed mid-response: {text:?}");
+        response.extend_from_slice(&chunk[..bytes_read]);
+    }
+}
+
+#[tokio::test]
+async fn test_keep_alive_serves_sequential_requests() {
+    let address: SocketAddr = spawn_server(ListenerOptions::default()).await;
+    let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+
+    for path in ["/a", "/b"] {
+        let raw: String = format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        stream.write_all(raw.as_bytes()).await.unwrap();
+
+        let response: String = read_response(&mut stream).await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(!response.contains("Connection: close"));
+        assert!(response.ends_with(&format!("GET {path} ")));
+    }
+}
+
+#[tokio::test]
+async fn test_connection_close_closes_after_response() {
+    let address: SocketAddr = spawn_server(ListenerOptions::default()).await;
+    let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+
+    stream
+        .write_all(b"GET /a HTTP/1.1\r\nHost: localhost\r\nConnection: keep-alive, close\r\n\r\n")
+        .await
+        .unwrap();
+
+    let started: Instant = Instant::now();
+    let response: String = read_all(&mut stream).await;
+
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("\r\nConnection: close\r\n"));
+    assert!(response.ends_with("GET /a "));
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_max_requests_per_connection_closes_connection() {
+    let address: SocketAddr = spawn_server(ListenerOptions {
+        max_requests_per_connection: 2,
+        ..Default::default()
+    })
+    .await;
+
+    let raw: &[u8] = b"GET /a HTTP/1.1\r\nHost: localhost\r\n\r\n\
+        GET /b HTTP/1.1\r\nHost: localhost\r\n\r\n\
+        GET /a HTTP/1.1\r\nHost: localhost\r\n\r\n";
+
+    let responses: String = send_and_read_all(address, raw).await;
+
+    assert_eq!(responses.matches("HTTP/1.1 200 OK\r\n").count(), 2);
+    assert_eq!(responses.matches("Connection: close\r\n").count(), 1);
+    assert!(responses.ends_with("Connection: close\r\nContent-Length: 7\r\n\r\nGET /b "));
+}
+
+#[tokio::test]
+async fn test_http_1_0_keep_alive_is_acknowledged() {
+    let address: SocketAddr = spawn_server(ListenerOptions::default()).await;
+    let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+
+    stream
+        .write_all(b"GET /a HTTP/1.0\r\nConnection: keep-alive\r\n\r\n")
+        .await
+        .unwrap();
+
+    let response: String = read_response(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.0 200 OK\r\n"));
+    assert!(response.contains("\r\nKeep-Alive: timeout=5, max=999\r\n"));
+    assert!(response.contains("\r\nConnection: keep-alive\r\n"));
+
+    stream.write_all(b"GET /b HTTP/1.0\r\n\r\n").await.unwrap();
+
+    let response: String = read_all(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.0 200 OK\r\n"));
+    assert!(response.contains("\r\nConnection: close\r\n"));
+    assert!(response.ends_with("GET /b "));
+}
+
+#[tokio::test]
+async fn test_http_1_0_errors_use_http_1_0_status_line() {
+    let address: SocketAddr = spawn_server(ListenerOptions::default()).await;
+
+    let response: String = send_and_read_all(address, b"GET /missing HTTP/1.0\r\n\r\n").await;
+    assert!(
+        response.starts_with("HTTP/1.0 404 Not Found\r\nConnection: close\r\n"),
+        "{response}"
+    );
+
+    let response: String = send_and_read_all(address, b"GET /a HTTP/1.0\r\nBroken header\r\n\r\n").await;
+    assert!(response.starts_with("HTTP/1.0 400 Bad Request\r\n"), "{response}");
+}
+
+#[tokio::test]
+async fn test_keep_alive_timeout_closes_idle_connection() {
+    let keep_alive_timeout: Duration = Duration::from_millis(200);
+    let address: SocketAddr = spawn_server(ListenerOptions {
+        timeouts: Timeouts {
+            keep_alive: keep_alive_timeout,
+            ..Timeouts::default()
+        },
+        ..Default::default()
+    })
+    .await;
+
+    let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+    stream
+        .write_all(b"GET /a HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+    assert!(read_response(&mut stream).await.starts_with("HTTP/1.1 200 OK\r\n"));
+
+    sleep(keep_alive_timeout / 4).await;
+    stream
+        .write_all(b"GET /b HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+    assert!(read_response(&mut stream).await.ends_with("GET /b "));
+
+    let started: Instant = Instant::now();
+    let remaining: String = read_all(&mut stream).await;
+
+    assert!(remaining.is_empty());
+    assert!(started.elapsed() >= keep_alive_timeout / 2);
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
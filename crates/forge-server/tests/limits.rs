@@ -0,0 +1,89 @@
+mod common;
+
+use std::net::SocketAddr;
+
+use common::{read_all, send_and_read_all, spawn_server};
+use forge_http::Limits;
+use forge_server::ListenerOptions;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+
+fn limit_options(limits: Limits) -> ListenerOptions {
+    ListenerOptions {
+        limits,
+        ..Default::default()
+    }
+}
+
+fn upload_request(path: &str, length: usize) -> Vec<u8> {
+    let mut raw: Vec<u8> =
+        format!("POST {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {length}\r\n\r\n")
+            .into_bytes();
+    raw.resize(raw.len() + length, b'x');
+    raw
+}
+
+#[tokio::test]
+async fn test_listener_limits_apply_before_routing() {
+    let address: SocketAddr = spawn_server(limit_options(Limits {
+        max_uri_length: 16,
+        max_header_size: 32,
+        ..Limits::default()
+    }))
+    .await;
+
+    let long_uri: String = send_and_read_all(address, b"GET /a?0123456789abcdef HTTP/1.1\r\nHost: a\r\n\r\n").await;
+    assert!(long_uri.starts_with("HTTP/1.1 414 URI Too Long\r\n"));
+
+    let large_header: String = send_and_read_all(
+        address,
+        b"GET /a HTTP/1.1\r\nHost: a\r\nX-Large: 0123456789abcdef0123456789\r\n\r\n",
+    )
+    .await;
+    assert!(large_header.starts_with("HTTP/1.1 431 Request Header Fields Too Large\r\n"));
+}
+
+#[tokio::test]
+async fn test_route_limits_cannot_raise_global_head_limits() {
+    let address: SocketAddr = spawn_server(limit_options(Limits {
+        max_uri_length: 16,
+        ..Limits::default()
+    }))
+    .await;
+
+    let response: String =
+        send_and_read_all(address, b"POST /upload?0123456789abcdef HTTP/1.1\r\nHost: a\r\n\r\n").await;
+    assert!(response.starts_with("HTTP/1.1 414 URI Too Long\r\n"));
+}
+
+#[tokio::test]
+async fn test_route_limits_override_body_size() {
+    let address: SocketAddr = spawn_server(limit_options(Limits {
+        max_body_size: 1024,
+        ..Limits::default()
+    }))
+    .await;
+
+    let rejected: String = send_and_read_all(address, &upload_request("/echo", 2048)).await;
+    assert!(rejected.starts_with("HTTP/1.1 413 Payload Too Large\r\n"));
+
+    let accepted: String = send_and_read_all(address, &upload_request("/upload", 2048)).await;
+    assert!(accepted.starts_with("HTTP/1.1 200 OK\r\n"));
+}
+
+#[tokio::test]
+async fn test_error_response_survives_unread_body() {
+    let address: SocketAddr = spawn_server(limit_options(Limits {
+        max_body_size: 1024,
+        ..Limits::default()
+    }))
+    .await;
+
+    for (path, status) in [("/echo", "413 Payload Too Large"), ("/missing", "404 Not Found")] {
+        let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+        stream.write_all(&upload_request(path, 32 * 1024)).await.unwrap();
+
+        let response: String = read_all(&mut stream).await;
+        assert!(response.starts_with(&format!("HTTP/1.1 {status}\r\n")), "{response}");
+    }
+}
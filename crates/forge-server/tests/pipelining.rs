@@ -0,0 +1,77 @@
+mod common;
+
+use std::net::SocketAddr;
+
+use common::{read_all, send_and_read_all, spawn_server};
+use forge_server::ListenerOptions;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+
+fn bodies(responses: &str) -> Vec<&str> {
+    responses
+        .split("HTTP/1.1 ")
+        .filter(|response: &&str| !response.is_empty())
+        .map(|response: &str| response.split_once("\r\n\r\n").unwrap().1)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_pipelined_requests_are_answered_in_order() {
+    let address: SocketAddr = spawn_server(ListenerOptions::default()).await;
+
+    let raw: &[u8] = b"GET /a HTTP/1.1\r\nHost: localhost\r\n\r\n\
+        GET /b HTTP/1.1\r\nHost: localhost\r\n\r\n\
+        GET /a HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+
+    let responses: String = send_and_read_all(address, raw).await;
+
+    assert_eq!(bodies(&responses), vec!["GET /a ", "GET /b ", "GET /a "]);
+    assert!(responses.ends_with("Connection: close\r\nContent-Length: 7\r\n\r\nGET /a "));
+}
+
+#[tokio::test]
+async fn test_pipelined_requests_with_bodies() {
+    let address: SocketAddr = spawn_server(ListenerOptions::default()).await;
+
+    let raw: &[u8] = b"POST /echo HTTP/1.1\r\nHost: localhost\r\nContent-Length: 5\r\n\r\nhello\
+        POST /echo HTTP/1.1\r\nHost: localhost\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n2\r\nde\r\n0\r\n\r\n\
+        GET /b HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+
+    let responses: String = send_and_read_all(address, raw).await;
+
+    assert_eq!(bodies(&responses), vec!["POST /echo hello", "POST /echo abcde", "GET /b "]);
+}
+
+#[tokio::test]
+async fn test_pipelined_request_split_across_writes() {
+    let address: SocketAddr = spawn_server(ListenerOptions::default()).await;
+    let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+
+    stream
+        .write_all(b"GET /a HTTP/1.1\r\nHost: localhost\r\n\r\nGET /b HTTP/1.1\r\nHo")
+        .await
+        .unwrap();
+    stream.flush().await.unwrap();
+
+    stream
+        .write_all(b"st: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+
+    let responses: String = read_all(&mut stream).await;
+
+    assert_eq!(bodies(&responses), vec!["GET /a ", "GET /b "]);
+}
+
+#[tokio::test]
+async fn test_error_closes_pipelined_connection() {
+    let address: SocketAddr = spawn_server(ListenerOptions::default()).await;
+
+    let raw: &[u8] = b"GET /missing HTTP/1.1\r\nHost: localhost\r\n\r\n\
+        GET /a HTTP/1.1\r\nHost: localhost\r\n\r\n";
+
+    let responses: String = send_and_read_all(address, raw).await;
+
+    assert!(responses.starts_with("HTTP/1.1 404 Not Found\r\nConnection: close\r\n"));
+    assert_eq!(responses.matches("HTTP/1.1 ").count(), 1);
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use std::io::Error;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+use common::{connect, free_port, read_all, spawn_listener, spawn_server_with_shutdown};
+use forge_server::ListenerOptions;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::oneshot;
+use tokio::sync::watch::{self, Receiver, Sender};
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, sleep};
+
+fn listener_options(port: u16, grace_period: Duration) -> ListenerOptions {
+    ListenerOptions {
+        port,
+        host: IpAddr::V4(Ipv4Addr::LOCALHOST),
+        shutdown_grace_period: grace_period,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_idle_keep_alive_connection_closes_on_shutdown() {
+    let (sender, receiver): (Sender<bool>, Receiver<bool>) = watch::channel(false);
+    let address: SocketAddr = spawn_server_with_shutdown(ListenerOptions::default(), Some(receiver)).await;
+    let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+
+    stream
+        .write_all(b"GET /a HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+
+    let mut buffer: [u8; 1024] = [0; 1024];
+    let length: usize = stream.read(&mut buffer).await.unwrap();
+    assert!(buffer[..length].ends_with(b"GET /a "));
+
+    sender.send(true).unwrap();
+
+    let started: Instant = Instant::now();
+    assert!(read_all(&mut stream).await.is_empty());
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_in_flight_request_finishes_with_connection_close() {
+    let (sender, receiver): (Sender<bool>, Receiver<bool>) = watch::channel(false);
+    let address: SocketAddr = spawn_server_with_shutdown(ListenerOptions::default(), Some(receiver)).await;
+    let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+
+    stream
+        .write_all(b"GET /slow HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+    sleep(Duration::from_millis(50)).await;
+    sender.send(true).unwrap();
+
+    let response: String = read_all(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("Connection: close\r\n"));
+    assert!(response.ends_with("slow"));
+}
+
+#[tokio::test]
+async fn test_listener_drains_and_stops_accepting() {
+    let port: u16 = free_port().await;
+    let (trigger, handle): (oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_listener(listener_options(port, Duration::from_secs(5)));
+
+    let mut stream: TcpStream = connect(port).await;
+    stream
+        .write_all(b"GET /slow HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+    sleep(Duration::from_millis(50)).await;
+
+    trigger.send(()).unwrap();
+
+    let response: String = read_all(&mut stream).await;
+    assert!(response.ends_with("slow"));
+
+    handle.await.unwrap().unwrap();
+    assert!(TcpStream::connect((Ipv4Addr::LOCALHOST, port)).await.is_err());
+}
+
+#[tokio::test]
+async fn test_listener_force_closes_after_grace_period() {
+    let port: u16 = free_port().await;
+    let (trigger, handle): (oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_listener(listener_options(port, Duration::from_millis(100)));
+
+    let mut stream: TcpStream = connect(port).await;
+    stream
+        .write_all(b"GET /slow HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+    sleep(Duration::from_millis(50)).await;
+
+    let started: Instant = Instant::now();
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+
+    assert!(started.elapsed() < Duration::from_millis(250));
+    assert!(read_all(&mut stream).await.is_empty());
+}
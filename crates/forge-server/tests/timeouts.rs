@@ -0,0 +1,92 @@
+mod common;
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use common::{read_all, spawn_server};
+use forge_server::{ListenerOptions, Timeouts};
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tokio::time::{Instant, sleep};
+
+fn short_timeouts() -> ListenerOptions {
+    ListenerOptions {
+        timeouts: Timeouts {
+            first_request: Duration::from_millis(200),
+            keep_alive: Duration::from_millis(200),
+            header_read: Duration::from_millis(200),
+            body_read: Duration::from_millis(200),
+            write: Duration::from_secs(1),
+        },
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_idle_connection_is_closed_silently() {
+    let address: SocketAddr = spawn_server(short_timeouts()).await;
+    let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+
+    let started: Instant = Instant::now();
+    let response: String = read_all(&mut stream).await;
+
+    assert!(response.is_empty());
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn test_trickled_headers_get_408() {
+    let address: SocketAddr = spawn_server(short_timeouts()).await;
+    let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+
+    for chunk in [&b"GET /a HTTP/1.1\r\n"[..], b"Host: localhost\r\n"] {
+        stream.write_all(chunk).await.unwrap();
+        sleep(Duration::from_millis(80)).await;
+    }
+
+    let response: String = read_all(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.1 408 Request Timeout\r\n"), "{response}");
+}
+
+#[tokio::test]
+async fn test_incomplete_body_gets_408() {
+    let address: SocketAddr = spawn_server(short_timeouts()).await;
+    let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+
+    stream
+        .write_all(b"POST /echo HTTP/1.1\r\nHost: localhost\r\nContent-Length: 10\r\n\r\nabc")
+        .await
+        .unwrap();
+
+    let response: String = read_all(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.1 408 Request Timeout\r\n"), "{response}");
+}
+
+#[tokio::test]
+async fn test_incomplete_chunked_body_gets_408() {
+    let address: SocketAddr = spawn_server(short_timeouts()).await;
+    let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+
+    stream
+        .write_all(b"POST /echo HTTP/1.1\r\nHost: localhost\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n")
+        .await
+        .unwrap();
+
+    let response: String = read_all(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.1 408 Request Timeout\r\n"), "{response}");
+}
+
+#[tokio::test]
+async fn test_keep_alive_timeout_closes_after_response() {
+    let address: SocketAddr = spawn_server(short_timeouts()).await;
+    let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+
+    stream
+        .write_all(b"GET /a HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+
+    let response: String = read_all(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.ends_with("GET /a "));
+}
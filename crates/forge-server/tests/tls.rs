@@ -0,0 +1,211 @@
+#![cfg(feature = "tls")]
+
+mod common;
+
+use std::io::Error;
+use std::sync::Arc;
+
+use common::{TestCertificate, connect, free_port, generate_certificate, spawn_listener};
+use forge_server::{ListenerOptions, OverloadPolicy, TlsOptions};
+use rustls::pki_types::{CertificateDer, ServerName};
+use rustls::{ClientConfig, RootCertStore, crypto::ring};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::client::TlsStream;
+
+#[cfg(feature = "http2")]
+use bytes::Bytes;
+#[cfg(feature = "http2")]
+use h2::client::{self, ResponseFuture, SendRequest};
+#[cfg(feature = "http2")]
+use h2::{RecvStream, SendStream};
+
+fn client_config(trusted: &TestCertificate, alpn_protocols: &[&[u8]]) -> ClientConfig {
+    let mut roots: RootCertStore = RootCertStore::empty();
+    roots.add(trusted.der.clone()).unwrap();
+
+    let mut config: ClientConfig = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    config.alpn_protocols = alpn_protocols
+        .iter()
+        .map(|protocol: &&[u8]| protocol.to_vec())
+        .collect();
+    config
+}
+
+async fn connect_tls(port: u16, server_name: &str, config: ClientConfig) -> Result<TlsStream<TcpStream>, Error> {
+    let stream: TcpStream = connect(port).await;
+    let server_name: ServerName<'static> = ServerName::try_from(server_name.to_owned()).unwrap();
+    TlsConnector::from(Arc::new(config)).connect(server_name, stream).await
+}
+
+async fn request(stream: &mut TlsStream<TcpStream>, path: &str) -> String {
+    let raw: String = format!("GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+    stream.write_all(raw.as_bytes()).await.unwrap();
+
+    let mut response: Vec<u8> = Vec::new();
+    stream.read_to_end(&mut response).await.unwrap();
+    String::from_utf8(response).unwrap()
+}
+
+fn tls_options(port: u16, tls: TlsOptions) -> ListenerOptions {
+    ListenerOptions {
+        port,
+        tls: Some(tls),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_https_request_is_served() {
+    let certificate: TestCertificate = generate_certificate("localhost");
+    let port: u16 = free_port().await;
+    let tls: TlsOptions = TlsOptions::new(&certificate.cert_path, &certificate.key_path);
+    let (_trigger, _handle) = spawn_listener(tls_options(port, tls));
+
+    let mut stream: TlsStream<TcpStream> = connect_tls(port, "localhost", client_config(&certificate, &[]))
+        .await
+        .unwrap();
+    let response: String = request(&mut stream, "/a").await;
+
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+    assert!(response.ends_with("GET /a "));
+}
+
+#[tokio::test]
+async fn test_alpn_negotiates_http_1_1() {
+    let certificate: TestCertificate = generate_certificate("localhost");
+    let port: u16 = free_port().await;
+    let tls: TlsOptions =
+        TlsOptions::new(&certificate.cert_path, &certificate.key_path).with_alpn_protocols(["http/1.1"]);
+    let (_trigger, _handle) = spawn_listener(tls_options(port, tls));
+
+    let config: ClientConfig = client_config(&certificate, &[b"h2", b"http/1.1"]);
+    let stream: TlsStream<TcpStream> = connect_tls(port, "localhost", config).await.unwrap();
+
+    assert_eq!(stream.get_ref().1.alpn_protocol(), Some(&b"http/1.1"[..]));
+}
+
+#[tokio::test]
+async fn test_alpn_mismatch_fails_handshake() {
+    let certificate: TestCertificate = generate_certificate("localhost");
+    let port: u16 = free_port().await;
+    let tls: TlsOptions =
+        TlsOptions::new(&certificate.cert_path, &certificate.key_path).with_alpn_protocols(["http/1.1"]);
+    let (_trigger, _handle) = spawn_listener(tls_options(port, tls));
+
+    let config: ClientConfig = client_config(&certificate, &[b"h2"]);
+    assert!(connect_tls(port, "localhost", config).await.is_err());
+}
+
+#[tokio::test]
+async fn test_sni_selects_certificate() {
+    let default: TestCertificate = generate_certificate("localhost");
+    let other: TestCertificate = generate_certificate("api.forge.test");
+    let port: u16 = free_port().await;
+
+    let tls: TlsOptions = TlsOptions::new(&default.cert_path, &default.key_path).with_sni_certificate(
+        "api.forge.test",
+        &other.cert_path,
+        &other.key_path,
+    );
+    let (_trigger, _handle) = spawn_listener(tls_options(port, tls));
+
+    let mut stream: TlsStream<TcpStream> = connect_tls(port, "api.forge.test", client_config(&other, &[]))
+        .await
+        .unwrap();
+
+    let presented: &CertificateDer = &stream.get_ref().1.peer_certificates().unwrap()[0];
+    assert_eq!(presented, &other.der);
+    assert!(request(&mut stream, "/b").await.ends_with("GET /b "));
+
+    let fallback: Result<TlsStream<TcpStream>, Error> =
+        connect_tls(port, "unknown.forge.test", client_config(&other, &[])).await;
+    assert!(fallback.is_err());
+}
+
+#[tokio::test]
+async fn test_plaintext_request_is_not_served() {
+    let certificate: TestCertificate = generate_certificate("localhost");
+    let port: u16 = free_port().await;
+    let tls: TlsOptions = TlsOptions::new(&certificate.cert_path, &certificate.key_path);
+    let (_trigger, _handle) = spawn_listener(tls_options(port, tls));
+
+    let mut stream: TcpStream = connect(port).await;
+    stream
+        .write_all(b"GET /a HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+
+    let mut response: Vec<u8> = Vec::new();
+    let _ = stream.read_to_end(&mut response).await;
+    assert!(!response.starts_with(b"HTTP/1.1"));
+}
+
+#[tokio::test]
+async fn test_rejected_connection_closes_before_handshake() {
+    let certificate: TestCertificate = generate_certificate("localhost");
+    let port: u16 = free_port().await;
+    let tls: TlsOptions = TlsOptions::new(&certificate.cert_path, &certificate.key_path);
+    let options: ListenerOptions = ListenerOptions {
+        max_connections: 1,
+        overload_policy: OverloadPolicy::Reject,
+        ..tls_options(port, tls)
+    };
+    let (_trigger, _handle) = spawn_listener(options);
+
+    let mut first: TlsStream<TcpStream> = connect_tls(port, "localhost", client_config(&certificate, &[]))
+        .await
+        .unwrap();
+    first
+        .write_all(b"GET /a HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+
+    let mut buffer: [u8; 1024] = [0; 1024];
+    let length: usize = first.read(&mut buffer).await.unwrap();
+    assert!(buffer[..length].starts_with(b"HTTP/1.1 200 OK\r\n"));
+
+    let second: Result<TlsStream<TcpStream>, Error> =
+        connect_tls(port, "localhost", client_config(&certificate, &[])).await;
+    assert!(second.is_err());
+}
+
+#[tokio::test]
+async fn test_missing_certificate_fails_to_start() {
+    let port: u16 = free_port().await;
+    let tls: TlsOptions = TlsOptions::new("/nonexistent/cert.pem", "/nonexistent/key.pem");
+    let (_trigger, handle) = spawn_listener(tls_options(port, tls));
+
+    assert!(handle.await.unwrap().is_err());
+}
+
+#[cfg(feature = "http2")]
+#[tokio::test]
+async fn test_alpn_negotiates_http2() {
+    let certificate: TestCertificate = generate_certificate("localhost");
+    let port: u16 = free_port().await;
+    let tls: TlsOptions = TlsOptions::new(&certificate.cert_path, &certificate.key_path);
+    let (_trigger, _handle) = spawn_listener(tls_options(port, tls));
+
+    let config: ClientConfig = client_config(&certificate, &[b"h2", b"http/1.1"]);
+    let stream: TlsStream<TcpStream> = connect_tls(port, "localhost", config).await.unwrap();
+    assert_eq!(stream.get_ref().1.alpn_protocol(), Some(&b"h2"[..]));
+
+    let (client, connection): (SendRequest<Bytes>, client::Connection<TlsStream<TcpStream>, Bytes>) =
+        client::handshake(stream).await.unwrap();
+    tokio::spawn(connection);
+
+    let request: http::Request<()> = http::Request::get("https://localhost/a").body(()).unwrap();
+    let (response, _): (ResponseFuture, SendStream<Bytes>) =
+        client.ready().await.unwrap().send_request(request, true).unwrap();
+    let response: http::Response<RecvStream> = response.await.unwrap();
+
+    assert_eq!(response.status(), http::StatusCode::OK);
+    assert_eq!(response.version(), http::Version::HTTP_2);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::echo_router;
+use forge_router::Router;
+use forge_server::{Connection, ListenerOptions, Rewind};
+use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream, duplex};
+use tokio::task::JoinHandle;
+
+fn serve_duplex(options: &ListenerOptions) -> (DuplexStream, JoinHandle<()>) {
+    let router: Arc<Router> = Arc::new(echo_router());
+    let (client, server): (DuplexStream, DuplexStream) = duplex(64 * 1024);
+    let connection: Connection<DuplexStream> = Connection::new(router, server, options);
+
+    (client, tokio::spawn(connection.serve()))
+}
+
+async fn read_all(client: &mut DuplexStream) -> String {
+    let mut response: Vec<u8> = Vec::new();
+    client.read_to_end(&mut response).await.unwrap();
+    String::from_utf8(response).unwrap()
+}
+
+#[tokio::test]
+async fn test_serves_in_memory_duplex_stream() {
+    let (mut client, handle): (DuplexStream, JoinHandle<()>) = serve_duplex(&ListenerOptions::default());
+
+    client
+        .write_all(b"POST /echo HTTP/1.1\r\nHost: localhost\r\nContent-Length: 5\r\n\r\nhello")
+        .await
+        .unwrap();
+    client
+        .write_all(b"GET /b HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+
+    let response: String = read_all(&mut client).await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("POST /echo hello"));
+    assert!(response.ends_with("GET /b "));
+
+    handle.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_serves_wrapped_transport() {
+    let router: Arc<Router> = Arc::new(echo_router());
+    let (mut client, server): (DuplexStream, DuplexStream) = duplex(64 * 1024);
+    let prefix: Vec<u8> = b"GET /a HTTP/1.1\r\nHost: localhost\r\n".to_vec();
+    let transport: Rewind<DuplexStream> = Rewind::new(prefix, server);
+    let connection: Connection<Rewind<DuplexStream>> = Connection::new(router, transport, &ListenerOptions::default());
+    let handle: JoinHandle<()> = tokio::spawn(connection.serve());
+
+    client.write_all(b"Connection: close\r\n\r\n").await.unwrap();
+
+    let response: String = read_all(&mut client).await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.ends_with("GET /a "));
+
+    handle.await.unwrap();
+}
@@ -0,0 +1,140 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::fs::{self, Metadata};
+use std::io::{Error, ErrorKind};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::UnixListener as StdUnixListener;
+use std::path::{Path, PathBuf};
+
+use common::{echo_router, spawn_listener};
+use forge_server::{Listener, ListenerOptions, UnixSocketOptions};
+use tempfile::TempDir;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::UnixStream;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, sleep};
+
+fn socket_path(directory: &TempDir, name: &str) -> PathBuf {
+    directory.path().join(format!("{name}.sock"))
+}
+
+fn unix_options(unix: UnixSocketOptions) -> ListenerOptions {
+    ListenerOptions {
+        unix: Some(unix),
+        ..Default::default()
+    }
+}
+
+async fn connect_unix(path: &Path) -> UnixStream {
+    for _ in 0..50 {
+        if let Ok(stream) = UnixStream::connect(path).await {
+            return stream;
+        }
+
+        sleep(Duration::from_millis(10)).await;
+    }
+
+    panic!("Listener did not start on {path:?}");
+}
+
+async fn request(path: &Path) -> String {
+    let mut stream: UnixStream = connect_unix(path).await;
+    stream
+        .write_all(b"GET /a HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+
+    let mut response: Vec<u8> = Vec::new();
+    stream.read_to_end(&mut response).await.unwrap();
+    String::from_utf8(response).unwrap()
+}
+
+#[tokio::test]
+async fn test_serves_requests_over_unix_socket() {
+    let directory: TempDir = TempDir::new().unwrap();
+    let path: PathBuf = socket_path(&directory, "serve");
+    let (trigger, handle): (oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_listener(unix_options(UnixSocketOptions::new(&path)));
+
+    let response: String = request(&path).await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.ends_with("GET /a "));
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn test_applies_socket_permissions() {
+    let directory: TempDir = TempDir::new().unwrap();
+    let path: PathBuf = socket_path(&directory, "mode");
+    let (trigger, handle): (oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_listener(unix_options(UnixSocketOptions::new(&path).with_mode(0o660)));
+
+    connect_unix(&path).await;
+    let metadata: Metadata = fs::metadata(&path).unwrap();
+    assert_eq!(metadata.permissions().mode() & 0o777, 0o660);
+    assert_eq!(fs::read_dir(directory.path()).unwrap().count(), 1);
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn test_removes_stale_socket() {
+    let directory: TempDir = TempDir::new().unwrap();
+    let path: PathBuf = socket_path(&directory, "stale");
+    drop(StdUnixListener::bind(&path).unwrap());
+    assert!(path.exists());
+
+    let (trigger, handle): (oneshot::Sender<()>, JoinHandle<Result<(), Error>>) =
+        spawn_listener(unix_options(UnixSocketOptions::new(&path)));
+
+    assert!(request(&path).await.starts_with("HTTP/1.1 200 OK\r\n"));
+
+    trigger.send(()).unwrap();
+    handle.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn test_refuses_socket_in_use() {
+    let directory: TempDir = TempDir::new().unwrap();
+    let path: PathBuf = socket_path(&directory, "in-use");
+    let _existing: StdUnixListener = StdUnixListener::bind(&path).unwrap();
+
+    let listener: Listener = Listener::new(echo_router(), unix_options(UnixSocketOptions::new(&path)));
+    let error: Error = listener.run_until(async {}).await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::AddrInUse);
+    assert!(path.exists());
+}
+
+#[tokio::test]
+async fn test_keeps_stale_socket_when_cleanup_disabled() {
+    let directory: TempDir = TempDir::new().unwrap();
+    let path: PathBuf = socket_path(&directory, "no-cleanup");
+    drop(StdUnixListener::bind(&path).unwrap());
+
+    let options: UnixSocketOptions = UnixSocketOptions::new(&path).with_remove_stale(false);
+    let listener: Listener = Listener::new(echo_router(), unix_options(options));
+    let error: Error = listener.run_until(async {}).await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::AddrInUse);
+}
+
+#[tokio::test]
+async fn test_mode_restricted_bind_never_replaces_existing_file() {
+    let directory: TempDir = TempDir::new().unwrap();
+    let path: PathBuf = socket_path(&directory, "occupied");
+    fs::write(&path, b"keep").unwrap();
+
+    let options: UnixSocketOptions = UnixSocketOptions::new(&path).with_mode(0o600).with_remove_stale(false);
+    let listener: Listener = Listener::new(echo_router(), unix_options(options));
+    let error: Error = listener.run_until(async {}).await.unwrap_err();
+
+    assert_eq!(error.kind(), ErrorKind::AddrInUse);
+    assert_eq!(fs::read(&path).unwrap(), b"keep");
+    assert_eq!(fs::read_dir(directory.path()).unwrap().count(), 1);
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use std::net::SocketAddr;
+
+use common::{read_all, spawn_server};
+use forge_http::{CloseFrame, Message, Request, Upgraded, WebSocket, WebSocketConfig};
+use forge_server::ListenerOptions;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+const KEY: &str = "dGhlIHNhbXBsZSBub25jZQ==";
+
+fn handshake(path: &str) -> String {
+    format!(
+        "GET {path} HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+         Sec-WebSocket-Version: 13\r\nSec-WebSocket-Key: {KEY}\r\n\r\n"
+    )
+}
+
+async fn read_head(stream: &mut TcpStream) -> String {
+    let mut head: Vec<u8> = Vec::new();
+
+    while Request::head_length(&head).is_none() {
+        let mut byte: [u8; 1] = [0];
+        assert_eq!(stream.read(&mut byte).await.unwrap(), 1, "connection closed during handshake");
+        head.push(byte[0]);
+    }
+
+    String::from_utf8(head).unwrap()
+}
+
+async fn connect(address: SocketAddr, path: &str) -> WebSocket {
+    let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+    stream.write_all(handshake(path).as_bytes()).await.unwrap();
+
+    let head: String = read_head(&mut stream).await;
+    assert!(head.starts_with("HTTP/1.1 101 Switching Protocols\r\n"), "{head}");
+    assert!(
+        head.contains("Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\n"),
+        "{head}"
+    );
+    assert!(!head.contains("Content-Length"), "{head}");
+
+    WebSocket::client(Upgraded::new(stream), WebSocketConfig::default())
+}
+
+#[tokio::test]
+async fn test_websocket_echo() {
+    let address: SocketAddr = spawn_server(ListenerOptions::default()).await;
+    let mut socket: WebSocket = connect(address, "/ws").await;
+
+    socket.send(Message::Text("hello".into())).await.unwrap();
+    assert_eq!(socket.recv().await.unwrap().unwrap(), Message::Text("hello".into()));
+
+    socket.send(Message::Binary(vec![1, 2, 3])).await.unwrap();
+    assert_eq!(socket.recv().await.unwrap().unwrap(), Message::Binary(vec![1, 2, 3]));
+
+    socket.send(Message::Ping(b"ping".to_vec())).await.unwrap();
+    assert_eq!(socket.recv().await.unwrap().unwrap(), Message::Pong(b"ping".to_vec()));
+}
+
+#[tokio::test]
+async fn test_websocket_close_handshake() {
+    let address: SocketAddr = spawn_server(ListenerOptions::default()).await;
+    let mut socket: WebSocket = connect(address, "/ws").await;
+
+    socket.close(1000, "done").await.unwrap();
+
+    let expected: Message = Message::Close(Some(CloseFrame {
+        code: 1000,
+        reason: "done".into(),
+    }));
+    assert_eq!(socket.recv().await.unwrap().unwrap(), expected);
+}
+
+#[tokio::test]
+async fn test_frame_sent_with_handshake_is_not_lost() {
+    let address: SocketAddr = spawn_server(ListenerOptions::default()).await;
+    let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+
+    let mut raw: Vec<u8> = handshake("/ws").into_bytes();
+    raw.extend_from_slice(&[0x81, 0x85, 0, 0, 0, 0]);
+    raw.extend_from_slice(b"early");
+    stream.write_all(&raw).await.unwrap();
+
+    let head: String = read_head(&mut stream).await;
+    assert!(head.starts_with("HTTP/1.1 101 Switching Protocols\r\n"), "{head}");
+
+    let mut socket: WebSocket = WebSocket::client(Upgraded::new(stream), WebSocketConfig::default());
+    assert_eq!(socket.recv().await.unwrap().unwrap(), Message::Text("early".into()));
+}
+
+#[tokio::test]
+async fn test_oversized_frame_closes_with_1009() {
+    let address: SocketAddr = spawn_server(ListenerOptions::default()).await;
+    let mut socket: WebSocket = connect(address, "/ws/small").await;
+
+    socket.send(Message::Text("x".repeat(17))).await.unwrap();
+
+    let close: Message = socket.recv().await.unwrap().unwrap();
+    assert!(
+        matches!(close, Message::Close(Some(CloseFrame { code: 1009, .. }))),
+        "{close:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_plain_request_to_websocket_route_gets_426() {
+    let address: SocketAddr = spawn_server(ListenerOptions::default()).await;
+    let mut stream: TcpStream = TcpStream::connect(address).await.unwrap();
+
+    stream
+        .write_all(b"GET /ws HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+
+    let response: String = read_all(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.1 426 Upgrade Required\r\n"), "{response}");
+}
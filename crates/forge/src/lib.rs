@@ -1,7 +1,16 @@
 pub mod prelude {
     pub use forge_config::{Config, ConfigError};
-    pub use forge_http::{Headers, HttpError, HttpStatus, Params, Request, Response};
+    pub use forge_http::{
+        CloseFrame, HeaderMap, HeaderValue, HttpError, HttpStatus, Limits, Message, Params, ParseOptions, Query,
+        Request, Response, TypedHeader, WebSocket, WebSocketConfig, typed_headers,
+    };
     pub use forge_macros::main;
-    pub use forge_router::{Router, delete, get, post, put, routes};
-    pub use forge_server::{Listener, ListenerOptions};
+    pub use forge_router::{Router, delete, get, post, put, routes, websocket};
+    #[cfg(feature = "http2")]
+    pub use forge_server::Http2Options;
+    #[cfg(unix)]
+    pub use forge_server::UnixSocketOptions;
+    pub use forge_server::{BoundListener, Listener, ListenerOptions};
+    #[cfg(feature = "tls")]
+    pub use forge_server::{TlsCertificate, TlsOptions};
 }
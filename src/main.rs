@@ -1,4 +1,5 @@
-use std::{net::Ipv4Addr, time::Duration};
+use std::net::{IpAddr, Ipv4Addr};
+use std::time::Duration;
 
 use forge::prelude::*;
 use serde_json::json;
@@ -10,7 +11,8 @@ async fn main() {
 
     let config: ListenerOptions = ListenerOptions {
         port: Config::from_env("PORT").unwrap_or(3000),
-        host: Config::from_env("HOST").unwrap_or_else(|_| Ipv4Addr::new(127, 0, 0, 1)),
+        host: Config::from_env("HOST").unwrap_or_else(|_| IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1))),
+        ..Default::default()
     };
 
     routes!(router, {
@@ -33,7 +35,7 @@ async fn user_handler(_: Request<'_>) -> Response<'_> {
 }
 
 fn ping_handler(req: Request) -> Response {
-    let headers: Headers = req.headers;
+    let headers: HeaderMap = req.headers;
     println!("Headers: {headers:#?}");
     Response::new(HttpStatus::Ok).text("pong!")
 }